@@ -0,0 +1,200 @@
+use crate::input::{asset_path, read_file};
+use crate::solution::{Answer, Part, Runner};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Recorded {
+    Text(String),
+    Number(u64),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecordedParts {
+    part_one: Option<Recorded>,
+    part_two: Option<Recorded>,
+}
+
+/// Known answers from a day's `answers.toml`, keyed by the input's file name
+/// under `assets` and then by part:
+///
+/// ```toml
+/// ["input.txt"]
+/// part_one = "2285373"
+/// part_two = "21142653"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    recorded: BTreeMap<(String, Part), String>,
+}
+
+/// How a freshly computed answer compares to the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String },
+    Unrecorded,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "matches the recorded answer"),
+            Verdict::Mismatch { expected } => write!(f, "expected {expected}"),
+            Verdict::Unrecorded => write!(f, "no recorded answer"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        let files: BTreeMap<String, RecordedParts> = toml::from_str(text)?;
+        let mut recorded = BTreeMap::new();
+        for (file, parts) in files {
+            for (part, value) in [(Part::One, parts.part_one), (Part::Two, parts.part_two)] {
+                let value = match value {
+                    Some(Recorded::Text(value)) => value,
+                    Some(Recorded::Number(value)) => value.to_string(),
+                    None => continue,
+                };
+                recorded.insert((file.clone(), part), value);
+            }
+        }
+        Ok(Answers { recorded })
+    }
+
+    /// Loads `path`, treating a missing file as having nothing recorded yet.
+    pub fn load(path: &Path) -> Result<Self, toml::de::Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text),
+            Err(_) => Ok(Answers::default()),
+        }
+    }
+
+    pub fn get(&self, file: &str, part: Part) -> Option<&str> {
+        self.recorded
+            .get(&(file.to_string(), part))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, file: &str, part: Part, answer: &Answer) -> Verdict {
+        match (self.get(file, part), answer) {
+            (None, _) => Verdict::Unrecorded,
+            (Some(expected), Answer::Solved(value)) if value == expected => Verdict::Match,
+            (Some(expected), _) => Verdict::Mismatch {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Part, &str)> {
+        self.recorded
+            .iter()
+            .map(|((file, part), value)| (file.as_str(), *part, value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recorded.is_empty()
+    }
+}
+
+/// Runs `runner` on every input recorded in `manifest_dir/answers.toml` and
+/// panics listing each answer that does not match.
+pub fn check_recorded(runner: &dyn Runner, manifest_dir: &str) {
+    let path = Path::new(manifest_dir).join("answers.toml");
+    let answers =
+        Answers::load(&path).unwrap_or_else(|err| panic!("Malformed {}: {err}", path.display()));
+    assert!(!answers.is_empty(), "No answers in {}", path.display());
+    let mut failures = Vec::new();
+    for (file, part, _) in answers.iter() {
+        let input = read_file(asset_path(manifest_dir, file));
+        let verdict = match runner.run(&input, part) {
+            Ok(answer) => answers.check(file, part, &answer),
+            Err(err) => {
+                failures.push(format!("{file} {part:?}: {err}"));
+                continue;
+            }
+        };
+        if verdict != Verdict::Match {
+            failures.push(format!("{file} {part:?}: {verdict}"));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "Day {} answers differ:\n{}",
+        runner.day(),
+        failures.join("\n")
+    );
+}
+
+/// Generates a test checking a day's [`Solution`](crate::solution::Solution)
+/// against its `answers.toml`.
+#[macro_export]
+macro_rules! test_answers {
+    ($solution:expr) => {
+        #[test]
+        fn it_matches_recorded_answers() {
+            $crate::answers::check_recorded(&$solution, env!("CARGO_MANIFEST_DIR"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{Answers, Verdict};
+    use crate::solution::{Answer, Part};
+
+    const RECORDED: &str = r#"
+["input.txt"]
+part_one = "4,6,3,5"
+part_two = 21142653
+
+["test-input.txt"]
+part_one = 11
+"#;
+
+    #[test]
+    fn it_reads_text_and_numbers_keyed_by_file_and_part() {
+        let answers = Answers::parse(RECORDED).unwrap();
+        assert_eq!(answers.get("input.txt", Part::One), Some("4,6,3,5"));
+        assert_eq!(answers.get("input.txt", Part::Two), Some("21142653"));
+        assert_eq!(answers.get("test-input.txt", Part::Two), None);
+        assert_eq!(answers.iter().count(), 3);
+    }
+
+    #[test]
+    fn it_compares_fresh_answers() {
+        let answers = Answers::parse(RECORDED).unwrap();
+        let check = |file, part, answer: Answer| answers.check(file, part, &answer);
+        assert_eq!(
+            check("test-input.txt", Part::One, 11.into()),
+            Verdict::Match
+        );
+        assert_eq!(
+            check("test-input.txt", Part::One, 12.into()),
+            Verdict::Mismatch {
+                expected: "11".to_string()
+            }
+        );
+        assert_eq!(
+            check("test-input.txt", Part::Two, 31.into()),
+            Verdict::Unrecorded
+        );
+        assert_eq!(
+            check("test-input.txt", Part::One, Answer::NoAnswer("11".into())),
+            Verdict::Mismatch {
+                expected: "11".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_parts() {
+        assert!(Answers::parse("[\"input.txt\"]\npart_three = 1\n").is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed position on a grid, `y` growing downwards like the puzzle text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    /// Steps to the four orthogonal neighbours, clockwise from north.
+    pub const ORTHOGONAL: [Point; 4] = [
+        Point::new(0, -1),
+        Point::new(1, 0),
+        Point::new(0, 1),
+        Point::new(-1, 0),
+    ];
+
+    /// Steps to all eight neighbours, clockwise from north.
+    pub const ADJACENT: [Point; 8] = [
+        Point::new(0, -1),
+        Point::new(1, -1),
+        Point::new(1, 0),
+        Point::new(1, 1),
+        Point::new(0, 1),
+        Point::new(-1, 1),
+        Point::new(-1, 0),
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.step()
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// One of the four orthogonal headings, as drawn by the `^>v<` arrows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every heading, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn step(self) -> Point {
+        Point::ORTHOGONAL[self.index()]
+    }
+
+    /// The heading moving by `(dx, dy)`, if that is a single orthogonal step.
+    pub fn from_delta(delta: (i32, i32)) -> Option<Direction> {
+        let step = Point::from(delta);
+        Direction::ALL.into_iter().find(|d| d.step() == step)
+    }
+
+    pub fn delta(self) -> (i32, i32) {
+        self.step().into()
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self.index()]
+    }
+}
+
+/// One of the eight compass points, including the diagonals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compass {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Compass {
+    /// Every compass point, clockwise from `North`.
+    pub const ALL: [Compass; 8] = [
+        Compass::North,
+        Compass::NorthEast,
+        Compass::East,
+        Compass::SouthEast,
+        Compass::South,
+        Compass::SouthWest,
+        Compass::West,
+        Compass::NorthWest,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn step(self) -> Point {
+        Point::ADJACENT[self.index()]
+    }
+
+    /// The compass point moving by `(dx, dy)`, if that is a single step.
+    pub fn from_delta(delta: (i32, i32)) -> Option<Compass> {
+        let step = Point::from(delta);
+        Compass::ALL.into_iter().find(|c| c.step() == step)
+    }
+
+    pub fn delta(self) -> (i32, i32) {
+        self.step().into()
+    }
+
+    /// Turns clockwise by 45 degrees.
+    pub fn turn_right(self) -> Compass {
+        Compass::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Turns anticlockwise by 45 degrees.
+    pub fn turn_left(self) -> Compass {
+        Compass::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Compass {
+        Compass::ALL[(self.index() + 4) % 8]
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Self {
+        Compass::ALL[direction.index() * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{Compass, Direction, Point};
+
+    #[test]
+    fn it_does_signed_arithmetic() {
+        let mut point = Point::new(2, 3);
+        point -= Point::new(5, 1);
+        assert_eq!(point, Point::new(-3, 2));
+        assert_eq!(-point * 2, Point::new(6, -4));
+        assert_eq!(point + Direction::Up, Point::new(-3, 1));
+    }
+
+    #[test]
+    fn it_turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Compass::North.turn_left(), Compass::NorthWest);
+        assert_eq!(Compass::SouthEast.opposite(), Compass::NorthWest);
+        assert_eq!(Compass::from(Direction::Down), Compass::South);
+    }
+
+    #[test]
+    fn it_converts_arrows_and_deltas() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_arrow(direction.arrow()), Some(direction));
+            assert_eq!(Direction::from_delta(direction.delta()), Some(direction));
+        }
+        assert_eq!(Direction::from_arrow('x'), None);
+        assert_eq!(Direction::from_delta((1, 1)), None);
+        assert_eq!(Compass::from_delta((1, 1)), Some(Compass::SouthEast));
+    }
+}
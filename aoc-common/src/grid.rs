@@ -0,0 +1,263 @@
+use crate::geometry::Point;
+use crate::parse::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid stored row by row in one flat vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one row per line, mapping every character through `cell`.
+    /// Characters mapped to `None` and ragged rows are reported as errors.
+    pub fn parse_with(
+        input: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Grid::parse_lines(input.lines().enumerate(), cell)
+    }
+
+    /// Like [`Grid::parse_with`] over lines numbered by their index in the
+    /// whole input, for grids that are one section of a larger file.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = (usize, &'a str)>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (index, line) in lines {
+            let mut row_width = 0;
+            for (offset, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| ParseError::at_char(index, line, offset, "unexpected tile"))?;
+                cells.push(value);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(ParseError::at(
+                        index,
+                        line,
+                        line,
+                        format!("expected {width} columns, found {row_width}"),
+                    ));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every position, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |offset| Point::new((offset % width) as i32, (offset / width) as i32))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.around(point, &Point::ORTHOGONAL)
+    }
+
+    /// All eight neighbours of `point` that lie inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.around(point, &Point::ADJACENT)
+    }
+
+    fn around<'a>(
+        &'a self,
+        point: Point,
+        steps: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        steps.iter().filter_map(move |&step| {
+            let next = point + step;
+            self.get(next).map(|value| (next, value))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// Cells from `from` onwards, moving by `step` until leaving the grid.
+    /// A diagonal step gives a diagonal view.
+    pub fn ray(&self, from: Point, step: Point) -> impl Iterator<Item = &T> {
+        let mut next = from;
+        std::iter::from_fn(move || {
+            let value = self.get(next)?;
+            next += step;
+            Some(value)
+        })
+    }
+
+    /// Position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_with(input, Some)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+/// Renders one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+
+    const GRID: &str = "#..\n.#.\n..E\n";
+
+    #[test]
+    fn it_parses_one_row_per_line() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(1), &['.', '#', '.']);
+        assert_eq!(grid.to_string(), GRID);
+    }
+
+    #[test]
+    fn it_checks_bounds() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!(grid.get(Point::new(2, 2)), Some(&'E'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn it_maps_characters_and_rejects_unknown_ones() {
+        let heights = Grid::parse_with("01\n23\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(heights[Point::new(1, 1)], 3);
+        let err = Grid::parse_with("01\n2x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn it_rejects_ragged_rows() {
+        let err = Grid::parse("#..\n.#\n").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn it_yields_neighbours_inside_the_grid() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn it_views_columns_and_diagonals() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!(grid.column(2).collect::<String>(), "..E");
+        let diagonal = grid.ray(Point::new(0, 0), Point::new(1, 1));
+        assert_eq!(diagonal.collect::<String>(), "##E");
+        assert_eq!(grid.find(&'E'), Some(Point::new(2, 2)));
+    }
+}
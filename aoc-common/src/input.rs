@@ -0,0 +1,57 @@
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+/// Resolves `filename` inside the `assets` directory of the crate at `manifest_dir`.
+pub fn asset_path(manifest_dir: &str, filename: &str) -> PathBuf {
+    Path::new(manifest_dir).join("assets").join(filename)
+}
+
+/// Path of `filename` in the calling crate's `assets` directory, whatever the
+/// working directory is.
+#[macro_export]
+macro_rules! asset_path {
+    ($filename:expr) => {
+        $crate::input::asset_path(env!("CARGO_MANIFEST_DIR"), $filename)
+    };
+}
+
+/// Reads `filename` from the calling crate's `assets` directory.
+#[macro_export]
+macro_rules! read_asset {
+    ($filename:expr) => {
+        $crate::input::read_file($crate::asset_path!($filename))
+    };
+}
+
+/// Reads a whole puzzle input, panicking with the path when it is missing.
+pub fn read_file(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    fs::read_to_string(path).unwrap_or_else(|err| panic!("Cannot read {}: {err}", path.display()))
+}
+
+/// Reads a whole puzzle input from any buffered reader, such as locked stdin.
+pub fn read_all(mut reader: impl BufRead) -> io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::read_all;
+    use std::io::Cursor;
+
+    #[test]
+    fn it_resolves_assets_from_the_manifest_dir() {
+        let path = asset_path!("input.txt");
+        assert!(path.starts_with(env!("CARGO_MANIFEST_DIR")));
+        assert!(path.ends_with("assets/input.txt"));
+    }
+
+    #[test]
+    fn it_reads_everything_from_a_reader() {
+        let input = read_all(Cursor::new("3   4\n4   3\n")).unwrap();
+        assert_eq!(input.lines().count(), 2);
+    }
+}
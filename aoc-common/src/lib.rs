@@ -0,0 +1,7 @@
+pub mod answers;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod search;
+pub mod solution;
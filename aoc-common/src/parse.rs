@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed puzzle input, pointing at the offending text.
+///
+/// `line` and `column` are one based, the way editors report positions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error at `token` on the line with zero based `index`, as yielded by
+    /// `input.lines().enumerate()`. `token` should be a slice of `line` so
+    /// its column can be recovered; otherwise the error points at column 1.
+    pub fn at(index: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| offset + token.len() <= line.len())
+            .unwrap_or(0);
+        let column = line[..offset].chars().count() + 1;
+        ParseError::new(index + 1, column, token, message)
+    }
+
+    /// Error at the character starting at byte `offset` of `line`, as yielded
+    /// by `line.char_indices()`.
+    pub fn at_char(index: usize, line: &str, offset: usize, message: impl Into<String>) -> Self {
+        let len = line[offset..].chars().next().map_or(0, char::len_utf8);
+        ParseError::at(index, line, &line[offset..offset + len], message)
+    }
+
+    /// Error for something the input never provided, such as a missing section.
+    pub fn end_of_input(input: &str, message: impl Into<String>) -> Self {
+        ParseError::new(input.lines().count() + 1, 1, "", message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " `{}`", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `line`, reporting its position on failure.
+pub fn parse_number<T>(index: usize, line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    token
+        .parse::<T>()
+        .map_err(|err| ParseError::at(index, line, token, format!("invalid number ({err})")))
+}
+
+/// Splits `line` around the first `delimiter`, failing when it is absent.
+pub fn split_once<'a>(
+    index: usize,
+    line: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    line.split_once(delimiter)
+        .ok_or_else(|| ParseError::at(index, line, line, format!("expected `{delimiter}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_number, split_once, ParseError};
+
+    #[test]
+    fn it_points_at_the_offending_token() {
+        let line = "3   x4";
+        let token = line.split_whitespace().last().unwrap();
+        let err = parse_number::<i32>(6, line, token).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (7, 5, "x4"));
+        assert_eq!(
+            err.to_string(),
+            "line 7, column 5: invalid number (invalid digit found in string) `x4`"
+        );
+    }
+
+    #[test]
+    fn it_reports_a_missing_delimiter() {
+        let err = split_once(0, "Register A 729", ": ").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, 1, "Register A 729", "expected `: `")
+        );
+    }
+
+    #[test]
+    fn it_reports_end_of_input_after_the_last_line() {
+        let err = ParseError::end_of_input("#.\n..\n", "no start");
+        assert_eq!(err.to_string(), "line 3, column 1: no start");
+    }
+}
@@ -0,0 +1,338 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The cheapest way found from the start to a goal, both ends included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route<S> {
+    pub cost: u64,
+    pub path: Vec<S>,
+}
+
+/// Like [`Route`], plus every state lying on at least one optimal path.
+#[derive(Clone, Debug)]
+pub struct BestRoutes<S> {
+    pub cost: u64,
+    pub path: Vec<S>,
+    pub states: HashSet<S>,
+}
+
+/// Breadth-first search where every step costs one.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+    let mut seen = HashSet::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            let path = trace_back(state, |s| parents.get(s));
+            let cost = path.len() as u64 - 1;
+            return Some(Route { cost, path });
+        }
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm over `neighbours` yielding each next state with the
+/// cost of moving there.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost for
+/// the route to be optimal.
+pub fn astar<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> u64,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Route<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let explored = explore(start, neighbours, heuristic, is_goal, false)?;
+    let path = trace_back(explored.goals[0].clone(), |s| {
+        explored.parents.get(s).map(|parents| &parents[0])
+    });
+    Some(Route {
+        cost: explored.cost,
+        path,
+    })
+}
+
+/// Dijkstra's algorithm that also collects every state on any optimal path
+/// to any goal reachable at the optimal cost.
+pub fn dijkstra_all<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<BestRoutes<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let explored = explore(start, neighbours, |_| 0, is_goal, true)?;
+    let path = trace_back(explored.goals[0].clone(), |s| {
+        explored.parents.get(s).map(|parents| &parents[0])
+    });
+    let mut states = HashSet::new();
+    let mut pending = explored.goals;
+    while let Some(state) = pending.pop() {
+        if states.contains(&state) {
+            continue;
+        }
+        if let Some(parents) = explored.parents.get(&state) {
+            pending.extend(parents.iter().cloned());
+        }
+        states.insert(state);
+    }
+    Some(BestRoutes {
+        cost: explored.cost,
+        path,
+        states,
+    })
+}
+
+struct Explored<S> {
+    cost: u64,
+    goals: Vec<S>,
+    parents: HashMap<S, Vec<S>>,
+}
+
+/// A queued state, ordered so the heap pops the lowest estimate first.
+struct Entry<S> {
+    estimate: u64,
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+/// Best-first search shared by Dijkstra and A*. With `all`, it keeps every
+/// equally cheap parent and carries on until no queued state can still
+/// reach a goal at the optimal cost.
+fn explore<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+    all: bool,
+) -> Option<Explored<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut best = HashMap::from([(start.clone(), 0)]);
+    let mut parents: HashMap<S, Vec<S>> = HashMap::new();
+    let mut heap = BinaryHeap::from([Entry {
+        estimate: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+    let mut found: Option<u64> = None;
+    let mut goals = Vec::new();
+    while let Some(Entry {
+        estimate,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        if found.is_some_and(|found| estimate > found) {
+            break;
+        }
+        if cost > best[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            found = Some(cost);
+            goals.push(state);
+            if !all {
+                break;
+            }
+            continue;
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            match best.get(&next) {
+                Some(&known) if next_cost > known => continue,
+                Some(&known) if next_cost == known => {
+                    // A zero-cost edge may lead back to an ancestor, the start
+                    // included; keeping it as a parent would close a cycle.
+                    if all && !(step == 0 && descends_from(&parents, &state, &next)) {
+                        parents.entry(next).or_default().push(state.clone());
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            best.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), vec![state.clone()]);
+            heap.push(Entry {
+                estimate: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+    Some(Explored {
+        cost: found?,
+        goals,
+        parents,
+    })
+}
+
+/// Whether `ancestor` is `state` or lies on one of its recorded parent chains.
+fn descends_from<S: Eq + Hash>(parents: &HashMap<S, Vec<S>>, state: &S, ancestor: &S) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = vec![state];
+    while let Some(state) = pending.pop() {
+        if state == ancestor {
+            return true;
+        }
+        if seen.insert(state) {
+            pending.extend(parents.get(state).into_iter().flatten());
+        }
+    }
+    false
+}
+
+fn trace_back<'a, S: Clone + 'a>(goal: S, mut parent: impl FnMut(&S) -> Option<&'a S>) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(previous) = parent(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+    use crate::search::{astar, bfs, dijkstra, dijkstra_all};
+
+    const MAZE: &str = "\
+S...
+.##.
+...E
+";
+
+    fn open_neighbours(grid: &Grid<char>, point: Point) -> impl Iterator<Item = Point> + '_ {
+        grid.neighbours4(point)
+            .filter(|(_, c)| **c != '#')
+            .map(|(p, _)| p)
+    }
+
+    #[test]
+    fn it_finds_the_shortest_path_breadth_first() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let end = grid.find(&'E').unwrap();
+        let route = bfs(
+            Point::new(0, 0),
+            |&p| open_neighbours(&grid, p),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(route.cost, 5);
+        assert_eq!(route.path.len(), 6);
+        assert_eq!(route.path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(route.path.last(), Some(&end));
+    }
+
+    #[test]
+    fn it_weighs_steps() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let end = grid.find(&'E').unwrap();
+        // Moving down the left edge is expensive, so the route goes right first.
+        let neighbours = |&p: &Point| {
+            open_neighbours(&grid, p)
+                .map(move |n| (n, if n.y > p.y && p.x == 0 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let route = dijkstra(Point::new(0, 0), neighbours, |&p| p == end).unwrap();
+        assert_eq!(route.cost, 5);
+        assert_eq!(route.path[1], Point::new(1, 0));
+        let manhattan = |p: &Point| ((end.x - p.x).abs() + (end.y - p.y).abs()) as u64;
+        let guided = astar(Point::new(0, 0), neighbours, manhattan, |&p| p == end).unwrap();
+        assert_eq!(guided, route);
+    }
+
+    #[test]
+    fn it_collects_every_state_on_an_optimal_path() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let end = grid.find(&'E').unwrap();
+        let neighbours = |&p: &Point| open_neighbours(&grid, p).map(|n| (n, 1));
+        let routes = dijkstra_all(Point::new(0, 0), neighbours, |&p| p == end).unwrap();
+        assert_eq!(routes.cost, 5);
+        assert_eq!(routes.states.len(), 10);
+        assert!(!routes.states.contains(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn it_ignores_zero_cost_edges_back_to_an_ancestor() {
+        let neighbours = |&s: &u8| match s {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+        let routes = dijkstra_all(0, neighbours, |&s| s == 2).unwrap();
+        assert_eq!((routes.cost, routes.path), (1, vec![0, 1, 2]));
+        assert_eq!(routes.states.len(), 3);
+        let route = dijkstra(0, neighbours, |&s| s == 2).unwrap();
+        assert_eq!(route.path, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn it_reports_unreachable_goals() {
+        let grid = Grid::parse("S#E\n").unwrap();
+        let end = Point::new(2, 0);
+        let neighbours = |&p: &Point| open_neighbours(&grid, p).map(|n| (n, 1));
+        assert!(dijkstra(Point::new(0, 0), neighbours, |&p| p == end).is_none());
+        assert!(bfs(
+            Point::new(0, 0),
+            |&p| open_neighbours(&grid, p),
+            |&p| p == end
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::parse::ParseError;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The input admits no answer, for the given reason.
+    NoAnswer(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Solved(value) => f.pad(value),
+            Answer::NoAnswer(_) => f.pad("none"),
+            Answer::Unsolved => f.pad("-"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Solved(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from!(i32, i64, u32, u64, usize, String, &str);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// A day of the calendar: how to read its input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(&self, input: &Self::Input) -> Answer;
+
+    fn part_two(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// Object safe view of a [`Solution`], so days with different inputs can share a registry.
+pub trait Runner {
+    fn day(&self) -> u8;
+
+    fn run(&self, input: &str, part: Part) -> Result<Answer, ParseError>;
+}
+
+impl<S: Solution> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        let input = self.parse(input)?;
+        Ok(match part {
+            Part::One => self.part_one(&input),
+            Part::Two => self.part_two(&input),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{parse_number, ParseError};
+    use crate::solution::{Answer, Part, Runner, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input = Vec<u32>;
+
+        fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| parse_number(i, line, line))
+                .collect()
+        }
+
+        fn part_one(&self, input: &Self::Input) -> Answer {
+            input.iter().sum::<u32>().into()
+        }
+    }
+
+    #[test]
+    fn it_runs_a_solution_through_the_runner() {
+        let runner: &dyn Runner = &Sum;
+        assert_eq!(runner.run("1\n2\n3", Part::One), Ok(Answer::from(6u32)));
+        assert_eq!(runner.run("1\n2\n3", Part::Two), Ok(Answer::Unsolved));
+    }
+
+    #[test]
+    fn it_surfaces_parse_errors_from_the_runner() {
+        let err = Sum.run("1\ntwo", Part::One).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
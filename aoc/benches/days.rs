@@ -0,0 +1,74 @@
+use aoc_common::solution::{Answer, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+
+/// Benchmarks parsing and each solved part of one day on its `input.txt`,
+/// skipping days whose input is not checked out.
+fn bench_day<S: Solution>(c: &mut Criterion, solution: S, dir: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(dir)
+        .join("assets")
+        .join("input.txt");
+    let Ok(text) = fs::read_to_string(&path) else {
+        eprintln!("Skipping day {}: no {}", S::DAY, path.display());
+        return;
+    };
+    let input = match solution.parse(&text) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Skipping day {}: invalid input: {err}", S::DAY);
+            return;
+        }
+    };
+    let mut group = c.benchmark_group(format!("day {:02}", S::DAY));
+    group.bench_function("parse", |b| b.iter(|| solution.parse(black_box(&text))));
+    if solution.part_one(&input) != Answer::Unsolved {
+        group.bench_function("part one", |b| {
+            b.iter(|| solution.part_one(black_box(&input)))
+        });
+    }
+    if solution.part_two(&input) != Answer::Unsolved {
+        group.bench_function("part two", |b| {
+            b.iter(|| solution.part_two(black_box(&input)))
+        });
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day(c, day_one::DayOne, "day-one");
+    bench_day(c, day_two::DayTwo, "day-two");
+    bench_day(c, day_three::DayThree, "day-three");
+    bench_day(c, day_four::DayFour, "day-four");
+    bench_day(c, day_five::DayFive, "day-five");
+    bench_day(c, day_six::DaySix, "day-six");
+    bench_day(c, day_seven::DaySeven, "day-seven");
+    bench_day(c, day_eight::DayEight, "day-eight");
+    bench_day(c, day_nine::DayNine, "day-nine");
+    bench_day(c, day_ten::DayTen, "day-ten");
+    bench_day(c, day_eleven::DayEleven, "day-eleven");
+    bench_day(c, day_twelve::DayTwelve, "day-twelve");
+    bench_day(c, day_thirteen::DayThirteen, "day-thirteen");
+    bench_day(c, day_fourteen::DayFourteen::default(), "day-fourteen");
+    bench_day(c, day_fifteen::DayFifteen, "day-fifteen");
+    bench_day(c, day_sixteen::DaySixteen, "day-sixteen");
+    bench_day(c, day_seventeen::DaySeventeen, "day-seventeen");
+    bench_day(c, day_eighteen::DayEighteen::default(), "day-eighteen");
+    bench_day(c, day_nineteen::DayNineteen, "day-nineteen");
+    bench_day(c, day_twentyone::DayTwentyOne, "day-twentyone");
+    bench_day(c, day_twentytwo::DayTwentyTwo, "day-twentytwo");
+    bench_day(c, day_twentythree::DayTwentyThree, "day-twentythree");
+    bench_day(c, day_twentyfour::DayTwentyFour, "day-twentyfour");
+    bench_day(c, day_twentyfive::DayTwentyFive, "day-twentyfive");
+}
+
+criterion_group! {
+    name = benches;
+    // Whole puzzles run for milliseconds, so fewer samples keep a full run short.
+    config = Criterion::default().sample_size(10);
+    targets = days
+}
+criterion_main!(benches);
@@ -0,0 +1,116 @@
+use aoc_common::answers::Answers;
+use aoc_common::solution::Runner;
+use std::path::{Path, PathBuf};
+
+const DAY_NAMES: [&str; 25] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+    "twentyone",
+    "twentytwo",
+    "twentythree",
+    "twentyfour",
+    "twentyfive",
+];
+
+fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day-{}", DAY_NAMES[day as usize - 1]))
+}
+
+pub fn default_input(day: u8) -> PathBuf {
+    day_dir(day).join("assets").join("input.txt")
+}
+
+/// The day's `answers.toml`, empty when nothing has been recorded yet.
+pub fn answers(day: u8) -> Answers {
+    let path = day_dir(day).join("answers.toml");
+    Answers::load(&path).unwrap_or_else(|err| {
+        eprintln!("Ignoring malformed {}: {err}", path.display());
+        Answers::default()
+    })
+}
+
+/// Every day with a [`Solution`](aoc_common::solution::Solution), in calendar order.
+pub fn registry() -> Vec<Box<dyn Runner>> {
+    vec![
+        Box::new(day_one::DayOne),
+        Box::new(day_two::DayTwo),
+        Box::new(day_three::DayThree),
+        Box::new(day_four::DayFour),
+        Box::new(day_five::DayFive),
+        Box::new(day_six::DaySix),
+        Box::new(day_seven::DaySeven),
+        Box::new(day_eight::DayEight),
+        Box::new(day_nine::DayNine),
+        Box::new(day_ten::DayTen),
+        Box::new(day_eleven::DayEleven),
+        Box::new(day_twelve::DayTwelve),
+        Box::new(day_thirteen::DayThirteen),
+        Box::new(day_fourteen::DayFourteen::default()),
+        Box::new(day_fifteen::DayFifteen),
+        Box::new(day_sixteen::DaySixteen),
+        Box::new(day_seventeen::DaySeventeen),
+        Box::new(day_eighteen::DayEighteen::default()),
+        Box::new(day_nineteen::DayNineteen),
+        Box::new(day_twentyone::DayTwentyOne),
+        Box::new(day_twentytwo::DayTwentyTwo),
+        Box::new(day_twentythree::DayTwentyThree),
+        Box::new(day_twentyfour::DayTwentyFour),
+        Box::new(day_twentyfive::DayTwentyFive),
+    ]
+}
+
+pub fn find(day: u8) -> Option<Box<dyn Runner>> {
+    registry().into_iter().find(|runner| runner.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::{answers, default_input, find, registry};
+    use aoc_common::solution::{Answer, Part};
+    use std::fs;
+
+    #[test]
+    fn it_solves_day_7_part_2() {
+        let input = fs::read_to_string(default_input(7).with_file_name("test-input.txt")).unwrap();
+        let result = find(7).unwrap().run(&input, Part::Two);
+        assert_eq!(result, Ok(Answer::from(11387u64)));
+    }
+
+    #[test]
+    fn it_loads_recorded_answers() {
+        let recorded = answers(1);
+        assert_eq!(recorded.get("input.txt", Part::One), Some("2285373"));
+        assert!(answers(20).is_empty());
+    }
+
+    #[test]
+    fn it_returns_none_for_unregistered_day() {
+        assert!(find(20).is_none());
+    }
+
+    #[test]
+    fn it_registers_days_in_order() {
+        let days = registry().iter().map(|r| r.day()).collect::<Vec<u8>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+    }
+}
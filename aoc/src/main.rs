@@ -0,0 +1,287 @@
+mod days;
+
+use aoc_common::answers::Verdict;
+use aoc_common::input::read_all;
+use aoc_common::solution::{Answer, Part};
+use clap::{Args, Parser, Subcommand};
+use day_seventeen::debugger::{run_session, Debugger};
+use day_seventeen::{disassembler, Vm};
+use day_sixteen::{render, render_to_file, Overlay};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every implemented day with --all
+    Run(RunArgs),
+    /// Trace, step through or disassemble the day 17 program
+    Debug(DebugArgs),
+    /// Draw the day 16 lowest-score paths over the maze
+    Maze(MazeArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present = "all")]
+    day: Option<u8>,
+    /// Part to solve, both parts are solved when omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Puzzle input, `-` reads stdin; defaults to the day's assets/input.txt
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Solve every implemented day in order and print a summary table
+    #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+    all: bool,
+}
+
+#[derive(Args)]
+struct DebugArgs {
+    /// Puzzle input, `-` reads stdin; defaults to day 17's assets/input.txt
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Print every executed instruction instead of starting the stepper
+    #[arg(long)]
+    trace: bool,
+    /// Print the program as mnemonics, and as pseudo-code when it is a single loop
+    #[arg(long, conflicts_with = "trace")]
+    disassemble: bool,
+    /// Most instructions to print with --trace
+    #[arg(long, default_value_t = 10_000, requires = "trace")]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct MazeArgs {
+    /// Puzzle input, `-` reads stdin; defaults to day 16's assets/input.txt
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Mark every tile on any lowest-score path with `O` instead of drawing one path
+    #[arg(long)]
+    seats: bool,
+    /// Write the drawing to this file instead of printing it
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) if args.all => {
+            run_all();
+            ExitCode::SUCCESS
+        }
+        Command::Run(args) => {
+            let day = args.day.expect("clap requires --day without --all");
+            let input = args.input.unwrap_or_else(|| days::default_input(day));
+            run_day(day, args.part, input)
+        }
+        Command::Debug(args) => debug(args),
+        Command::Maze(args) => maze(args),
+    }
+}
+
+fn run_day(day: u8, part: Option<u8>, path: PathBuf) -> ExitCode {
+    let Some(runner) = days::find(day) else {
+        eprintln!("Day {day} is not implemented");
+        return ExitCode::FAILURE;
+    };
+    let Ok(input) = read_input(&path) else {
+        eprintln!("Input file not found: {}", path.display());
+        return ExitCode::FAILURE;
+    };
+    let parts = match part {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => vec![Part::One, Part::Two],
+    };
+    let answers = days::answers(day);
+    let file = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("-");
+    let mut status = ExitCode::SUCCESS;
+    for part in parts {
+        let number = part_number(part);
+        match runner.run(&input, part) {
+            Ok(answer @ Answer::Solved(_)) => {
+                let verdict = answers.check(file, part, &answer);
+                println!("Day {day} part {number}: {answer} ({verdict})");
+                if let Verdict::Mismatch { .. } = verdict {
+                    status = ExitCode::FAILURE;
+                }
+            }
+            Ok(Answer::NoAnswer(reason)) => {
+                eprintln!("Day {day} part {number} has no answer: {reason}");
+                status = ExitCode::FAILURE;
+            }
+            Ok(Answer::Unsolved) => {
+                eprintln!("Day {day} part {number} is not implemented");
+                status = ExitCode::FAILURE;
+            }
+            Err(err) => {
+                eprintln!("Invalid input {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn debug(args: DebugArgs) -> ExitCode {
+    let path = args.input.unwrap_or_else(|| days::default_input(17));
+    let Ok(input) = read_input(&path) else {
+        eprintln!("Input file not found: {}", path.display());
+        return ExitCode::FAILURE;
+    };
+    let (registers, program) = match day_seventeen::parse_input(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("Invalid input {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.disassemble {
+        print!("{}", disassembler::listing(&program));
+        if let Some(code) = disassembler::decompile(&program) {
+            print!("\n{code}");
+        }
+        return ExitCode::SUCCESS;
+    }
+    let mut vm = Vm::new(&program, registers);
+    if args.trace {
+        for entry in vm.trace(args.limit) {
+            println!("{entry}");
+        }
+        if !vm.is_halted() {
+            println!("still running after {} instructions", args.limit);
+        }
+        println!("{}", day_seventeen::format_output(&vm.output));
+        return ExitCode::SUCCESS;
+    }
+    if path == Path::new("-") {
+        eprintln!("The stepper reads commands from stdin, pass the program with a file path");
+        return ExitCode::FAILURE;
+    }
+    let mut debugger = Debugger::new(vm);
+    match run_session(&mut debugger, io::stdin().lock(), io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Debugger stopped: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn maze(args: MazeArgs) -> ExitCode {
+    let path = args.input.unwrap_or_else(|| days::default_input(16));
+    let Ok(input) = read_input(&path) else {
+        eprintln!("Input file not found: {}", path.display());
+        return ExitCode::FAILURE;
+    };
+    let game = match day_sixteen::parse_input(&input) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("Invalid input {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let overlay = if args.seats {
+        Overlay::BestSeats
+    } else {
+        Overlay::Path
+    };
+    match args.output {
+        Some(output) => {
+            if let Err(err) = render_to_file(&game, overlay, &output) {
+                eprintln!("Cannot write {}: {err}", output.display());
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", render(&game, overlay)),
+    }
+    ExitCode::SUCCESS
+}
+
+fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        read_all(io::stdin().lock())
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+fn check_mark(verdict: &Verdict, answer: &Answer) -> &'static str {
+    match (verdict, answer) {
+        (_, Answer::Unsolved) => "-",
+        (_, Answer::NoAnswer(_)) => "FAIL",
+        (Verdict::Match, _) => "ok",
+        (Verdict::Mismatch { .. }, _) => "FAIL",
+        (Verdict::Unrecorded, _) => "new",
+    }
+}
+
+fn run_all() {
+    println!(
+        "{:>3} | {:<20} | {:<20} | {:<9} | {:>10}",
+        "Day", "Part 1", "Part 2", "Check", "Time"
+    );
+    println!("{:-<4}+{:-<22}+{:-<22}+{:-<11}+{:-<11}", "", "", "", "", "");
+    let mut total = Duration::ZERO;
+    for runner in days::registry() {
+        let day = runner.day();
+        let Ok(input) = fs::read_to_string(days::default_input(day)) else {
+            println!(
+                "{day:>3} | {:<20} | {:<20} | {:<9} | {:>10}",
+                "missing input", "", "", ""
+            );
+            continue;
+        };
+        let start = Instant::now();
+        let answers = runner
+            .run(&input, Part::One)
+            .and_then(|part_1| Ok((part_1, runner.run(&input, Part::Two)?)));
+        let elapsed = start.elapsed();
+        let (part_1, part_2) = match answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("Day {day}: {err}");
+                println!(
+                    "{day:>3} | {:<20} | {:<20} | {:<9} | {:>10}",
+                    "invalid input", "", "", ""
+                );
+                continue;
+            }
+        };
+        total += elapsed;
+        let answers = days::answers(day);
+        let check = format!(
+            "{} {}",
+            check_mark(&answers.check("input.txt", Part::One, &part_1), &part_1),
+            check_mark(&answers.check("input.txt", Part::Two, &part_2), &part_2)
+        );
+        println!(
+            "{day:>3} | {part_1:<20} | {part_2:<20} | {check:<9} | {:>10}",
+            format!("{elapsed:.2?}")
+        );
+    }
+    println!("{:-<4}+{:-<22}+{:-<22}+{:-<11}+{:-<11}", "", "", "", "", "");
+    println!("{:>61} | {:>10}", "Total", format!("{total:.2?}"));
+}
@@ -1,77 +1,162 @@
+use aoc_common::geometry::Point;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+#[derive(Debug)]
+pub struct AntennaMap {
+    pub map: Grid<char>,
+    pub antenna_frequencies: HashSet<char>,
 }
 
-#[derive(Debug)]
-struct AntennaMap {
-    map: Vec<Vec<char>>,
-    antenna_frequencies: HashSet<char>,
+pub fn parse_input_to_vectors(input: &str) -> Result<AntennaMap, ParseError> {
+    let map = Grid::parse(input)?;
+    let antenna_frequencies = map.iter().map(|(_, c)| *c).filter(|c| *c != '.').collect();
+    Ok(AntennaMap {
+        map,
+        antenna_frequencies,
+    })
 }
 
-fn parse_input_to_vectors(filename: PathBuf) -> AntennaMap {
-    let mut map = Vec::new();
-    let mut antenna_frequencies = HashSet::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let c = line
-                    .chars()
-                    .map(|x| {
-                        if x != '.' {
-                            antenna_frequencies.insert(x);
-                        }
-                        x
-                    })
-                    .collect::<Vec<char>>();
-                map.push(c);
+/// Antinodes of one frequency. Each ordered pair of antennas casts one
+/// antinode beyond the second antenna at the same distance; with `resonant`
+/// harmonics it keeps casting along the line, from the antenna itself until
+/// leaving the grid.
+pub fn frequency_antinodes(
+    antenna_map: &AntennaMap,
+    frequency: char,
+    resonant: bool,
+) -> HashSet<Point> {
+    let antennas = antenna_map
+        .map
+        .iter()
+        .filter(|(_, c)| **c == frequency)
+        .map(|(point, _)| point)
+        .collect::<Vec<_>>();
+    let mut antinodes = HashSet::new();
+    for &from in &antennas {
+        for &to in &antennas {
+            if from == to {
+                continue;
+            }
+            let step = to - from;
+            if resonant {
+                let line = std::iter::successors(Some(to), |&p| Some(p + step));
+                antinodes.extend(line.take_while(|&p| antenna_map.map.contains(p)));
+            } else if antenna_map.map.contains(to + step) {
+                antinodes.insert(to + step);
             }
         }
     }
-    AntennaMap {
-        map,
-        antenna_frequencies,
+    antinodes
+}
+
+pub fn antinodes(antenna_map: &AntennaMap, resonant: bool) -> HashSet<Point> {
+    antenna_map
+        .antenna_frequencies
+        .iter()
+        .flat_map(|&frequency| frequency_antinodes(antenna_map, frequency, resonant))
+        .collect()
+}
+
+/// Draws the map the way the puzzle text does: `#` where an antinode lands,
+/// unless an antenna already stands there. With `frequency`, only that
+/// frequency's antennas and antinodes are drawn.
+pub fn render(antenna_map: &AntennaMap, resonant: bool, frequency: Option<char>) -> String {
+    let antinodes = match frequency {
+        Some(frequency) => frequency_antinodes(antenna_map, frequency, resonant),
+        None => antinodes(antenna_map, resonant),
+    };
+    let mut overlay = antenna_map.map.map(|&c| match frequency {
+        Some(frequency) if c != frequency => '.',
+        _ => c,
+    });
+    for point in antinodes {
+        if overlay[point] == '.' {
+            overlay[point] = '#';
+        }
     }
+    overlay.to_string()
 }
 
-fn puzzle_1(filename: PathBuf) -> i32 {
-    let mut result = 0;
-    let mut antenna_map = parse_input_to_vectors(filename);
-    println!("{:?}", antenna_map);
-    result
+pub fn puzzle_1(antenna_map: &AntennaMap) -> usize {
+    antinodes(antenna_map, false).len()
+}
+
+pub fn puzzle_2(antenna_map: &AntennaMap) -> usize {
+    antinodes(antenna_map, true).len()
+}
+
+pub struct DayEight;
+
+impl Solution for DayEight {
+    const DAY: u8 = 8;
+    type Input = AntennaMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_vectors(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, puzzle_1};
+    use crate::{parse_input_to_vectors, render};
+
+    aoc_common::test_answers!(crate::DayEight);
 
     #[test]
-    fn it_returns_14() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let result = puzzle_1(file_path);
-        assert_eq!(result, 14);
+    fn it_renders_the_worked_examples() {
+        let input = include_str!("../assets/test-input.txt");
+        let data = parse_input_to_vectors(input).unwrap();
+        let antinodes = "\
+......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.
+";
+        assert_eq!(render(&data, false, None), antinodes);
+        let harmonics = "\
+##....#....#
+.#.#....0...
+..#.#0....#.
+..##...0....
+....0....#..
+.#...#A....#
+...#..#.....
+#....#.#....
+..#.....A...
+....#....A..
+.#........#.
+...#......##
+";
+        assert_eq!(render(&data, true, None), harmonics);
     }
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let result = puzzle_1(file_path);
-        assert_eq!(result, 0);
+    fn it_renders_a_single_frequency() {
+        let input = include_str!("../assets/test-input.txt");
+        let data = parse_input_to_vectors(input).unwrap();
+        let rendered = render(&data, false, Some('A'));
+        assert!(!rendered.contains('0'));
+        assert_eq!(rendered.matches('A').count(), 3);
+        assert_eq!(rendered.matches('#').count(), 5);
+        assert_eq!(rendered.lines().nth(2), Some("....#......."));
     }
 }
@@ -1,160 +1,278 @@
-use std::collections::VecDeque;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use aoc_common::geometry::Point;
+use aoc_common::grid::Grid;
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::search::{bfs, Route};
+use aoc_common::solution::{Answer, Solution};
+use std::fmt;
 
 #[derive(Debug)]
-struct Coord {
+pub struct Coord {
     x: usize,
     y: usize,
 }
 
-fn parse_input(filename: PathBuf) -> Vec<Coord> {
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The memory grid and the points the walk goes between, both kept inside it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemorySpace {
+    width: usize,
+    height: usize,
+    start: Point,
+    end: Point,
+}
+
+impl MemorySpace {
+    /// A `width` by `height` space walked from the top left corner to the
+    /// bottom right one, as in the puzzle. Panics if the space is empty.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Empty {width}x{height} memory space"
+        );
+        MemorySpace {
+            width,
+            height,
+            start: Point::new(0, 0),
+            end: Point::new(width as i32 - 1, height as i32 - 1),
+        }
+    }
+
+    /// The same space walked from `start` to `end`, or `None` if either lies
+    /// outside it.
+    pub fn with_route(self, start: Point, end: Point) -> Option<Self> {
+        (self.contains_point(start) && self.contains_point(end)).then_some(MemorySpace {
+            start,
+            end,
+            ..self
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    pub fn end(&self) -> Point {
+        self.end
+    }
+
+    pub fn contains(&self, coord: &Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        (0..self.width as i32).contains(&point.x) && (0..self.height as i32).contains(&point.y)
+    }
+}
+
+/// Reads the falling bytes, rejecting any that would land outside `space`.
+pub fn parse_input(input: &str, space: &MemorySpace) -> Result<Vec<Coord>, ParseError> {
     let mut coords: Vec<Coord> = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let coord = line
-                    .split(",")
-                    .map(|coord| coord.parse::<usize>().unwrap())
-                    .collect::<Vec<usize>>();
-                coords.push(Coord {
-                    x: coord[0],
-                    y: coord[1],
-                });
-            }
+    for (i, line) in input.lines().enumerate() {
+        let (x, y) = split_once(i, line, ",")?;
+        let coord = Coord {
+            x: parse_number(i, line, x)?,
+            y: parse_number(i, line, y)?,
+        };
+        if !space.contains(&coord) {
+            return Err(ParseError::at(
+                i,
+                line,
+                line,
+                format!(
+                    "byte falls outside the {}x{} memory space",
+                    space.width, space.height
+                ),
+            ));
         }
+        coords.push(coord);
     }
-    coords
+    Ok(coords)
 }
 
-type MemoryMap = Vec<Vec<char>>;
-fn init_map(mat: &MemoryMap, bytes: &[Coord]) -> MemoryMap {
-    let mut map = mat.clone();
+pub type MemoryMap = Grid<char>;
+
+fn init_map(space: &MemorySpace, bytes: &[Coord]) -> MemoryMap {
+    let mut map = Grid::new(space.width, space.height, '.');
     for byte in bytes {
-        map[byte.y][byte.x] = '#';
+        map[Point::new(byte.x as i32, byte.y as i32)] = '#';
     }
-    map[0][0] = 'S';
-    map[mat[0].len() - 1][mat.len() - 1] = 'E';
     map
 }
 
-type MemoryGraph = Vec<(usize, u32, (usize, usize))>;
-
-fn coord_i32(v: (usize, usize), dir: (i32, i32)) -> (i32, i32) {
-    ((v.0 as i32 + dir.0), (v.1 as i32 + dir.1))
+fn explore(start: Point, end: Point, map: &MemoryMap) -> Option<Route<Point>> {
+    let neighbours = |&point: &Point| {
+        map.neighbours4(point)
+            .filter(|(_, tile)| **tile != '#')
+            .map(|(next, _)| next)
+    };
+    bfs(start, neighbours, |&point| point == end)
 }
 
-fn coord_u32(c: (i32, i32)) -> (usize, usize) {
-    (c.0 as usize, c.1 as usize)
+/// The fewest steps to the exit once `fall_bytes` bytes have fallen, or
+/// `None` when they cut it off.
+pub fn puzzle_1(data: &[Coord], space: &MemorySpace, fall_bytes: usize) -> Option<usize> {
+    let map = init_map(space, &data[0..fall_bytes.min(data.len())]);
+    explore(space.start, space.end, &map).map(|route| route.cost as usize)
 }
 
-fn get_adj_edg(v: (usize, usize), map: &MemoryMap) -> Vec<(usize, usize)> {
-    let mut adj_edg = Vec::new();
-    let directions = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-    for direction in directions {
-        let coord = coord_i32(v, direction);
-        let value = if coord.0 >= 0
-            && coord.1 >= 0
-            && coord.0 < (map[0].len()) as i32
-            && coord.1 < (map.len()) as i32
-        {
-            Some(map[coord.1 as usize][coord.0 as usize])
+/// The first byte whose fall cuts the exit off from the start, or `None`
+/// when the exit stays reachable or is unreachable before any byte falls.
+/// Blocking only gets worse as bytes fall, so this binary searches the number
+/// of fallen bytes.
+pub fn puzzle_2<'a>(data: &'a [Coord], space: &MemorySpace) -> Option<&'a Coord> {
+    let reachable = |fallen: usize| {
+        let map = init_map(space, &data[0..fallen]);
+        explore(space.start, space.end, &map).is_some()
+    };
+    // Reachable with `low` bytes fallen, cut off with `high` once known.
+    let (mut low, mut high) = (0, data.len());
+    if !reachable(low) || reachable(high) {
+        return None;
+    }
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if reachable(middle) {
+            low = middle;
         } else {
-            None
-        };
-        if let Some(value) = value {
-            if value == '.' || value == 'E' {
-                adj_edg.push(coord_u32(coord));
-            }
+            high = middle;
         }
     }
-    adj_edg
+    Some(&data[high - 1])
+}
+
+pub struct DayEighteen {
+    pub space: MemorySpace,
+    pub fall_bytes: usize,
 }
 
-fn explore(
-    start: (usize, usize),
-    end: (usize, usize),
-    map: &MemoryMap,
-) -> Option<Vec<(usize, usize)>> {
-    let mut queue = VecDeque::new();
-    let mut path = Vec::new();
-    let mut visited = vec![vec![None; map[0].len()]; map.len()];
-    queue.push_front(start);
-    visited[0][0] = Some(start);
-    while !queue.is_empty() {
-        let vertex = queue.pop_front();
-        if let Some(value) = vertex {
-            if value == end {
-                path.push(value);
-                let mut prev = value;
-                while prev != start {
-                    let coord = visited[prev.1][prev.0].unwrap();
-                    path.push(coord);
-                    prev = coord;
-                }
-                return Some(path.into_iter().rev().collect::<Vec<_>>());
-            }
-            let adjacent_edges = get_adj_edg(value, &map);
-            for adjacent in adjacent_edges {
-                if visited[adjacent.1][adjacent.0].is_none() {
-                    visited[adjacent.1][adjacent.0] = Some(value);
-                    queue.push_back(adjacent);
-                }
-            }
+impl Default for DayEighteen {
+    fn default() -> Self {
+        DayEighteen {
+            space: MemorySpace::new(71, 71),
+            fall_bytes: 1024,
         }
     }
-    None
 }
 
-fn puzzle_1(data: Vec<Coord>, matrix_size: (usize, usize), fall_bytes: usize) -> usize {
-    let mut matrix = vec![vec!['.'; matrix_size.1]; matrix_size.0];
-    let bytes = &data[0..fall_bytes];
-    let map = init_map(&mut matrix, bytes);
-    let path = explore((0, 0), (matrix_size.0 - 1, matrix_size.1 - 1), &map);
-    //display_map(&map);
-    if let Some(path) = path {
-        //trace_map(&map, &path);
-        return path.iter().count() - 1;
-    }
-    0
+impl Solution for DayEighteen {
+    const DAY: u8 = 18;
+    type Input = Vec<Coord>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input, &self.space)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        match puzzle_1(input, &self.space, self.fall_bytes) {
+            Some(steps) => steps.into(),
+            None => Answer::NoAnswer("the exit is cut off".to_string()),
+        }
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        match puzzle_2(input, &self.space) {
+            Some(byte) => byte.to_string().into(),
+            None => Answer::NoAnswer("the exit is never cut off".to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_1, puzzle_2, DayEighteen, MemorySpace};
+    use aoc_common::geometry::Point;
+    use aoc_common::solution::{Answer, Solution};
+
+    aoc_common::test_answers!(crate::DayEighteen::default());
 
     #[test]
     fn it_returns_22() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let matrix_size = (7, 7);
-        let result = puzzle_1(data, matrix_size, 12);
-        assert_eq!(result, 22);
+        let input = include_str!("../assets/test-input.txt");
+        let space = MemorySpace::new(7, 7);
+        let data = parse_input(input, &space).unwrap();
+        let result = puzzle_1(&data, &space, 12);
+        assert_eq!(result, Some(22));
+    }
+
+    #[test]
+    fn it_returns_6_1() {
+        let input = include_str!("../assets/test-input.txt");
+        let space = MemorySpace::new(7, 7);
+        let data = parse_input(input, &space).unwrap();
+        let result = puzzle_2(&data, &space).map(|byte| byte.to_string());
+        assert_eq!(result.as_deref(), Some("6,1"));
+        assert!(puzzle_2(&data[..20], &space).is_none());
     }
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let matrix_size = (71, 71);
-        let result = puzzle_1(data, matrix_size, 1024);
-        assert_eq!(result, 338);
+    fn it_walks_rectangular_spaces_between_any_points() {
+        let space = MemorySpace::new(5, 3)
+            .with_route(Point::new(0, 2), Point::new(4, 0))
+            .unwrap();
+        let data = parse_input("1,0\n1,1\n3,2\n3,1\n2,1\n", &space).unwrap();
+        assert_eq!(puzzle_1(&data, &space, 4), Some(6));
+        assert_eq!(puzzle_1(&data, &space, 5), None);
+        assert_eq!(
+            puzzle_2(&data, &space)
+                .map(|byte| byte.to_string())
+                .as_deref(),
+            Some("2,1")
+        );
+    }
+
+    #[test]
+    fn it_reports_bytes_outside_the_space() {
+        let space = MemorySpace::new(7, 3);
+        let err = parse_input("6,2\n2,3\n", &space).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "2,3"));
+        assert_eq!(
+            err.message,
+            "byte falls outside the 7x3 memory space".to_string()
+        );
+    }
+
+    #[test]
+    fn it_rejects_routes_leaving_the_space() {
+        let space = MemorySpace::new(5, 3);
+        assert!(space
+            .with_route(Point::new(0, 0), Point::new(5, 2))
+            .is_none());
+        assert!(space
+            .with_route(Point::new(-1, 0), Point::new(4, 2))
+            .is_none());
+    }
+
+    #[test]
+    fn it_finds_no_cutting_byte_without_bytes() {
+        assert!(puzzle_2(&[], &MemorySpace::new(1, 1)).is_none());
+        let day = DayEighteen {
+            space: MemorySpace::new(1, 1),
+            fall_bytes: 0,
+        };
+        assert!(matches!(day.part_two(&vec![]), Answer::NoAnswer(_)));
+    }
+
+    #[test]
+    fn it_tells_a_cut_off_exit_from_an_empty_walk() {
+        assert_eq!(puzzle_1(&[], &MemorySpace::new(1, 1), 0), Some(0));
+        let day = DayEighteen {
+            space: MemorySpace::new(3, 1),
+            fall_bytes: 1,
+        };
+        let data = parse_input("1,0\n", &day.space).unwrap();
+        assert!(matches!(day.part_one(&data), Answer::NoAnswer(_)));
     }
 }
@@ -1,53 +1,38 @@
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use std::cmp::Ordering;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
+pub type Rules = Vec<(i32, i32)>;
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+pub type PrintingSequences = Vec<Vec<i32>>;
 
-fn parse_input_to_vectors(head: PathBuf, body: PathBuf) -> (Vec<(i32, i32)>, Vec<Vec<i32>>) {
-    let mut rules: Vec<(i32, i32)> = Vec::new();
-    let mut printing: Vec<Vec<i32>> = Vec::new();
-    if let Ok(lines) = read_lines(head) {
-        for line in lines {
-            if let Ok(line) = line {
-                let parsed = line
-                    .split("|")
-                    .map(|x| x.parse::<i32>().unwrap())
-                    .collect::<Vec<i32>>();
-                rules.push((parsed[0], parsed[1]));
-            }
+pub fn parse_input_to_vectors(input: &str) -> Result<(Rules, PrintingSequences), ParseError> {
+    let mut rules: Rules = Vec::new();
+    let mut printing: PrintingSequences = Vec::new();
+    let mut parse_printing = false;
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            parse_printing = true;
+            continue;
         }
-    }
-    if let Ok(lines) = read_lines(body) {
-        for line in lines {
-            if let Ok(line) = line {
-                let parsed = line
-                    .split(",")
-                    .map(|x| x.parse::<i32>().unwrap())
-                    .collect::<Vec<i32>>();
-                printing.push(parsed);
-            }
+        if parse_printing {
+            let parsed = line
+                .split(",")
+                .map(|x| parse_number(i, line, x))
+                .collect::<Result<Vec<i32>, _>>()?;
+            printing.push(parsed);
+        } else {
+            let (before, after) = split_once(i, line, "|")?;
+            rules.push((
+                parse_number(i, line, before)?,
+                parse_number(i, line, after)?,
+            ));
         }
     }
-    (rules, printing)
+    Ok((rules, printing))
 }
 
-fn is_breaking_the_rules(rules: Vec<(i32, i32)>, printing_sequence: &Vec<i32>) -> bool {
+fn is_breaking_the_rules(rules: Vec<(i32, i32)>, printing_sequence: &[i32]) -> bool {
     let mut breaking_rules = false;
     for (idx, page) in printing_sequence.iter().enumerate() {
         let rules_to_check = rules
@@ -57,7 +42,7 @@ fn is_breaking_the_rules(rules: Vec<(i32, i32)>, printing_sequence: &Vec<i32>) -
             .collect::<Vec<i32>>();
         let sub_seq = &printing_sequence[(idx + 1)..];
         for page in sub_seq {
-            if !rules_to_check.contains(&page) {
+            if !rules_to_check.contains(page) {
                 breaking_rules = true;
             }
         }
@@ -65,10 +50,10 @@ fn is_breaking_the_rules(rules: Vec<(i32, i32)>, printing_sequence: &Vec<i32>) -
     breaking_rules
 }
 
-fn puzzle_1(rules: Vec<(i32, i32)>, printing_sequences: Vec<Vec<i32>>) -> i32 {
+pub fn puzzle_1(rules: &[(i32, i32)], printing_sequences: &[Vec<i32>]) -> i32 {
     let mut data = Vec::new();
     for printing_sequence in printing_sequences {
-        if !is_breaking_the_rules(rules.clone(), &printing_sequence) {
+        if !is_breaking_the_rules(rules.to_vec(), printing_sequence) {
             let middle_page = printing_sequence[printing_sequence.len() / 2];
             data.push(middle_page);
         }
@@ -77,21 +62,21 @@ fn puzzle_1(rules: Vec<(i32, i32)>, printing_sequences: Vec<Vec<i32>>) -> i32 {
 }
 
 fn get_unorderer_printing_sequences(
-    rules: &Vec<(i32, i32)>,
-    printing_sequences: Vec<Vec<i32>>,
+    rules: &[(i32, i32)],
+    printing_sequences: &[Vec<i32>],
 ) -> Vec<Vec<i32>> {
     let mut data = Vec::new();
     for printing_sequence in printing_sequences {
-        if is_breaking_the_rules(rules.clone(), &printing_sequence) {
-            data.push(printing_sequence);
+        if is_breaking_the_rules(rules.to_vec(), printing_sequence) {
+            data.push(printing_sequence.clone());
         }
     }
     data
 }
 
-fn puzzle_2(rules: Vec<(i32, i32)>, printing_sequences: Vec<Vec<i32>>) -> i32 {
+pub fn puzzle_2(rules: &[(i32, i32)], printing_sequences: &[Vec<i32>]) -> i32 {
     let mut middle_pages = Vec::new();
-    let unordered_rules = get_unorderer_printing_sequences(&rules, printing_sequences);
+    let unordered_rules = get_unorderer_printing_sequences(rules, printing_sequences);
     for unordered_rule in unordered_rules {
         let mut reorderer_rule = unordered_rule.clone();
         reorderer_rule.sort_by(|a, b| {
@@ -107,43 +92,28 @@ fn puzzle_2(rules: Vec<(i32, i32)>, printing_sequences: Vec<Vec<i32>>) -> i32 {
     middle_pages.iter().sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_file_path, parse_input_to_vectors, puzzle_1, puzzle_2};
+pub struct DayFive;
 
-    #[test]
-    fn it_returns_143() {
-        let head_file_path = get_file_path("test-input-head.txt".to_string());
-        let body_file_path = get_file_path("test-input-body.txt".to_string());
-        let (rules, printing_sequence) = parse_input_to_vectors(head_file_path, body_file_path);
-        let result = puzzle_1(rules, printing_sequence);
-        assert_eq!(result, 143);
-    }
+impl Solution for DayFive {
+    const DAY: u8 = 5;
+    type Input = (Rules, PrintingSequences);
 
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let head_file_path = get_file_path("input-head.txt".to_string());
-        let body_file_path = get_file_path("input-body.txt".to_string());
-        let (rules, printing_sequence) = parse_input_to_vectors(head_file_path, body_file_path);
-        let result = puzzle_1(rules, printing_sequence);
-        assert_eq!(result, 4924);
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_vectors(input)
     }
 
-    #[test]
-    fn it_returns_123() {
-        let head_file_path = get_file_path("test-input-head.txt".to_string());
-        let body_file_path = get_file_path("test-input-body.txt".to_string());
-        let (rules, printing_sequence) = parse_input_to_vectors(head_file_path, body_file_path);
-        let result = puzzle_2(rules, printing_sequence);
-        assert_eq!(result, 123);
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        let (rules, printing_sequences) = input;
+        puzzle_1(rules, printing_sequences).into()
     }
 
-    #[test]
-    fn it_returns_puzzle_2_score() {
-        let head_file_path = get_file_path("input-head.txt".to_string());
-        let body_file_path = get_file_path("input-body.txt".to_string());
-        let (rules, printing_sequence) = parse_input_to_vectors(head_file_path, body_file_path);
-        let result = puzzle_2(rules, printing_sequence);
-        assert_eq!(result, 6085);
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        let (rules, printing_sequences) = input;
+        puzzle_2(rules, printing_sequences).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    aoc_common::test_answers!(crate::DayFive);
+}
@@ -1,85 +1,55 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-#[derive(Debug)]
-struct Robot {
+#[derive(Clone, Debug)]
+pub struct Robot {
     position: (i32, i32),
     speed: (i32, i32),
 }
 
-fn parse_input(filename: PathBuf) -> Vec<Robot> {
+fn parse_vector(
+    index: usize,
+    line: &str,
+    token: &str,
+    prefix: &str,
+) -> Result<(i32, i32), ParseError> {
+    let values = token
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(index, line, token, format!("expected `{prefix}`")))?;
+    let (x, y) = values
+        .split_once(",")
+        .ok_or_else(|| ParseError::at(index, line, values, "expected `,`"))?;
+    Ok((parse_number(index, line, x)?, parse_number(index, line, y)?))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Robot>, ParseError> {
     let mut robots = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for (y, line) in lines.enumerate() {
-            if let Ok(line) = line {
-                let [ref position, ref speed] = line
-                    .split_whitespace()
-                    .map(|pattern| {
-                        if pattern.starts_with("p") {
-                            pattern
-                                .strip_prefix("p=")
-                                .unwrap()
-                                .split(",")
-                                .map(|s| s.parse::<i32>().unwrap())
-                                .collect::<Vec<i32>>()
-                        } else if pattern.starts_with("v") {
-                            pattern
-                                .strip_prefix("v=")
-                                .unwrap()
-                                .split(",")
-                                .map(|s| s.parse::<i32>().unwrap())
-                                .collect::<Vec<i32>>()
-                        } else {
-                            panic!("Invalid line: {}", line);
-                        }
-                    })
-                    .collect::<Vec<Vec<i32>>>()[..]
-                else {
-                    panic!("Error while parsing line to position and speed")
-                };
-                robots.push(Robot {
-                    position: (position[0], position[1]),
-                    speed: (speed[0], speed[1]),
-                });
-            }
-        }
+    for (i, line) in input.lines().enumerate() {
+        let (position, speed) = split_once(i, line, " ")?;
+        robots.push(Robot {
+            position: parse_vector(i, line, position, "p=")?,
+            speed: parse_vector(i, line, speed, "v=")?,
+        });
     }
-    robots
+    Ok(robots)
 }
 
-fn puzzle_1(robots: &mut Vec<Robot>, width: i32, height: i32, iteration: i32) -> i32 {
-    for i in 0..iteration {
+pub fn puzzle_1(robots: &mut Vec<Robot>, width: i32, height: i32, iteration: i32) -> i32 {
+    for _ in 0..iteration {
         for robot in &mut *robots {
             let mut next_x = robot.position.0 + robot.speed.0;
             let mut next_y = robot.position.1 + robot.speed.1;
             if next_x > width - 1 {
-                next_x = next_x - width;
+                next_x -= width;
             }
             if next_x < 0 {
-                next_x = width + next_x;
+                next_x += width;
             }
             if next_y > height - 1 {
-                next_y = next_y - height;
+                next_y -= height;
             }
             if next_y < 0 {
-                next_y = height + next_y;
+                next_y += height;
             }
             robot.position = (next_x, next_y);
         }
@@ -100,29 +70,50 @@ fn puzzle_1(robots: &mut Vec<Robot>, width: i32, height: i32, iteration: i32) ->
         if curr.position.0 > mid_width_line && curr.position.1 > mid_height_line {
             acc.3 += 1;
         }
-        return acc;
+        acc
     });
 
     counters.0 * counters.1 * counters.2 * counters.3
 }
 
+pub struct DayFourteen {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Default for DayFourteen {
+    fn default() -> Self {
+        DayFourteen {
+            width: 101,
+            height: 103,
+        }
+    }
+}
+
+impl Solution for DayFourteen {
+    const DAY: u8 = 14;
+    type Input = Vec<Robot>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(&mut input.clone(), self.width, self.height, 100).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_1};
+
+    aoc_common::test_answers!(crate::DayFourteen::default());
 
     #[test]
     fn it_returns_12() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let mut data = parse_input(file_path);
+        let input = include_str!("../assets/test-input.txt");
+        let mut data = parse_input(input).unwrap();
         let result = puzzle_1(&mut data, 11, 7, 100);
         assert_eq!(result, 12);
     }
-
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let mut data = parse_input(file_path);
-        let result = puzzle_1(&mut data, 101, 103, 100);
-        assert_eq!(result, 218433348);
-    }
 }
@@ -1,23 +1,6 @@
 use crate::BlockKind::{FileCell, FreeCell};
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 
 #[derive(Clone, Debug, PartialEq)]
 enum BlockKind {
@@ -25,46 +8,46 @@ enum BlockKind {
     FileCell,
 }
 
-#[derive(Debug, PartialEq)]
-struct Block {
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
     kind: BlockKind,
     index: usize,
 }
 
-fn parse_input_to_blocks(filename: PathBuf) -> Vec<Block> {
+pub fn parse_input_to_blocks(input: &str) -> Result<Vec<Block>, ParseError> {
     let mut data = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let mut idx_file_block = 0;
-                for (i, char) in line.chars().enumerate() {
-                    if i % 2 == 0 {
-                        let block_size = char.to_digit(10).unwrap();
-                        for _ in 0..block_size {
-                            let block = Block {
-                                kind: FileCell,
-                                index: idx_file_block,
-                            };
-                            data.push(block);
-                        }
-                        idx_file_block += 1;
-                    } else {
-                        let block_size = char.to_digit(10).unwrap();
-                        for _ in 0..block_size {
-                            data.push(Block {
-                                kind: FreeCell,
-                                index: i,
-                            })
-                        }
-                    }
+    for (line_idx, line) in input.lines().enumerate() {
+        let mut idx_file_block = 0;
+        for (i, char) in line.char_indices() {
+            let block_size = char
+                .to_digit(10)
+                .ok_or_else(|| ParseError::at_char(line_idx, line, i, "expected a block size"))?;
+            if i % 2 == 0 {
+                for _ in 0..block_size {
+                    let block = Block {
+                        kind: FileCell,
+                        index: idx_file_block,
+                    };
+                    data.push(block);
+                }
+                idx_file_block += 1;
+            } else {
+                for _ in 0..block_size {
+                    data.push(Block {
+                        kind: FreeCell,
+                        index: i,
+                    })
                 }
             }
         }
     }
-    data
+    Ok(data)
 }
 
-fn shrink_cells(mut data: Vec<Block>) -> Vec<Block> {
+pub fn shrink_cells(mut data: Vec<Block>) -> Vec<Block> {
+    if data.is_empty() {
+        return data;
+    }
     let mut idx_right_to_left = data.len() - 1;
     let breakpoint = data.len() - data.iter().filter(|b| b.kind == FreeCell).count();
     for i in 0..data.len() {
@@ -84,7 +67,42 @@ fn shrink_cells(mut data: Vec<Block>) -> Vec<Block> {
     data
 }
 
-fn puzzle_1(data: Vec<Block>) -> u64 {
+/// Moves each whole file, highest ID first, into the leftmost free span
+/// before it that is long enough, leaving it in place when none is.
+pub fn move_files(mut data: Vec<Block>) -> Vec<Block> {
+    let mut files = Vec::new();
+    let mut free = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        let block = &data[start];
+        let len = data[start..]
+            .iter()
+            .take_while(|b| b.kind == block.kind && (b.kind == FreeCell || b.index == block.index))
+            .count();
+        match block.kind {
+            FileCell => files.push((start, len)),
+            FreeCell => free.push((start, len)),
+        }
+        start += len;
+    }
+    for &(file_start, file_len) in files.iter().rev() {
+        let Some(span) = free
+            .iter_mut()
+            .take_while(|(free_start, _)| *free_start < file_start)
+            .find(|(_, free_len)| *free_len >= file_len)
+        else {
+            continue;
+        };
+        for offset in 0..file_len {
+            data.swap(span.0 + offset, file_start + offset);
+        }
+        span.0 += file_len;
+        span.1 -= file_len;
+    }
+    data
+}
+
+pub fn checksum(data: &[Block]) -> u64 {
     let mut result = 0;
     for (position, block) in data.iter().enumerate() {
         if block.kind == FileCell {
@@ -95,25 +113,44 @@ fn puzzle_1(data: Vec<Block>) -> u64 {
     result
 }
 
+pub fn puzzle_1(data: &[Block]) -> u64 {
+    checksum(&shrink_cells(data.to_vec()))
+}
+
+pub fn puzzle_2(data: &[Block]) -> u64 {
+    checksum(&move_files(data.to_vec()))
+}
+
+pub struct DayNine;
+
+impl Solution for DayNine {
+    const DAY: u8 = 9;
+    type Input = Vec<Block>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_blocks(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input_to_blocks, puzzle_1, shrink_cells};
+    use crate::{parse_input_to_blocks, puzzle_1, puzzle_2};
 
-    #[test]
-    fn it_returns_1928() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input_to_blocks(file_path);
-        let cells = shrink_cells(data);
-        let result = puzzle_1(cells);
-        assert_eq!(result, 1928);
-    }
+    aoc_common::test_answers!(crate::DayNine);
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input_to_blocks(file_path);
-        let cells = shrink_cells(data);
-        let result = puzzle_1(cells);
-        assert_eq!(result, 6301895872542);
+    fn it_handles_a_disk_without_blocks() {
+        for input in ["", "0"] {
+            let data = parse_input_to_blocks(input).unwrap();
+            assert_eq!((puzzle_1(&data), puzzle_2(&data)), (0, 0));
+        }
     }
 }
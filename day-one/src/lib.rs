@@ -1,44 +1,21 @@
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn parse_input_to_vectors(filename: PathBuf) -> (Vec<i32>, Vec<i32>) {
+pub fn parse_input_to_vectors(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut left_vec: Vec<i32> = Vec::new();
     let mut right_vec: Vec<i32> = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let parsed = line.split("   ").collect::<Vec<&str>>();
-                let num_1: i32 = parsed[0].parse().expect("Cannot parse input");
-                let num_2: i32 = parsed[1].parse().expect("Cannot parse input");
-                left_vec.push(num_1);
-                right_vec.push(num_2);
-            }
-        }
+    for (i, line) in input.lines().enumerate() {
+        let (left, right) = split_once(i, line, "   ")?;
+        left_vec.push(parse_number(i, line, left)?);
+        right_vec.push(parse_number(i, line, right.trim())?);
     }
-    (left_vec, right_vec)
+    Ok((left_vec, right_vec))
 }
 
-fn puzzle_1(filename: PathBuf) -> i32 {
+pub fn puzzle_1(data: &(Vec<i32>, Vec<i32>)) -> i32 {
     let mut result = 0;
-    let (mut left_vec, mut right_vec) = parse_input_to_vectors(filename);
+    let (mut left_vec, mut right_vec) = data.clone();
     left_vec.sort();
     right_vec.sort();
     for i in 0..left_vec.len() {
@@ -48,9 +25,9 @@ fn puzzle_1(filename: PathBuf) -> i32 {
     result
 }
 
-fn puzzle_2(filename: PathBuf) -> i32 {
+pub fn puzzle_2(data: &(Vec<i32>, Vec<i32>)) -> i32 {
     let mut result = 0;
-    let (left_vec, right_vec) = parse_input_to_vectors(filename);
+    let (left_vec, right_vec) = data;
     let mut count_hashmap = HashMap::new();
     for value in right_vec {
         count_hashmap
@@ -59,7 +36,7 @@ fn puzzle_2(filename: PathBuf) -> i32 {
             .or_insert(1);
     }
     for l_value in left_vec {
-        let right_value = count_hashmap.get(&l_value);
+        let right_value = count_hashmap.get(l_value);
         if let Some(r_value) = right_value {
             let sim_score = r_value * l_value;
             result += sim_score;
@@ -68,35 +45,26 @@ fn puzzle_2(filename: PathBuf) -> i32 {
     result
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_file_path, puzzle_1, puzzle_2};
+pub struct DayOne;
 
-    #[test]
-    fn it_returns_11() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let result = puzzle_1(file_path);
-        assert_eq!(result, 11);
-    }
+impl Solution for DayOne {
+    const DAY: u8 = 1;
+    type Input = (Vec<i32>, Vec<i32>);
 
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let result = puzzle_1(file_path);
-        assert_eq!(result, 2285373);
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_vectors(input)
     }
 
-    #[test]
-    fn it_returns_31() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let result = puzzle_2(file_path);
-        assert_eq!(result, 31);
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
     }
 
-    #[test]
-    fn it_returns_puzzle_2_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let result = puzzle_2(file_path);
-        assert_eq!(result, 21142653);
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    aoc_common::test_answers!(crate::DayOne);
+}
@@ -1,52 +1,39 @@
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use radix_fmt::Radix;
 use std::collections::HashSet;
 use std::fmt::Write;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
-struct Assertion {
+#[derive(Debug)]
+pub struct Assertion {
     result: u64,
     operands: Vec<u64>,
 }
 
-fn parse_input_to_assertions(input: PathBuf) -> Vec<Assertion> {
+pub fn parse_input_to_assertions(input: &str) -> Result<Vec<Assertion>, ParseError> {
     let mut assertions = Vec::new();
-    if let Ok(lines) = read_lines(input) {
-        for line in lines {
-            if let Ok(line) = line {
-                let parsed = line.split(":").collect::<Vec<&str>>();
-                let result = parsed[0].parse::<u64>().unwrap();
-                let operands = parsed[1]
-                    .trim()
-                    .split(" ")
-                    .map(|x| x.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>();
-                let assertion = Assertion { result, operands };
-                assertions.push(assertion);
-            }
+    for (i, line) in input.lines().enumerate() {
+        let (result, operands) = split_once(i, line, ":")?;
+        let result = parse_number(i, line, result)?;
+        let operands = operands
+            .split_whitespace()
+            .map(|x| parse_number(i, line, x))
+            .collect::<Result<Vec<u64>, _>>()?;
+        if operands.is_empty() {
+            return Err(ParseError::at(
+                i,
+                line,
+                line,
+                "expected at least one operand",
+            ));
         }
+        let assertion = Assertion { result, operands };
+        assertions.push(assertion);
     }
-    assertions
+    Ok(assertions)
 }
 
-fn puzzle_1(assertions: Vec<Assertion>) -> u64 {
+pub fn puzzle_1(assertions: &[Assertion]) -> u64 {
     let mut sum = 0;
     for assertion in assertions {
         let op_seq = generate_possible_operator_sequences(assertion.operands.len() - 1);
@@ -55,9 +42,9 @@ fn puzzle_1(assertions: Vec<Assertion>) -> u64 {
                 assertion.operands[0],
                 |acc, (idx, x)| {
                     if seq[idx] == '+' {
-                        return acc + *x;
+                        acc + *x
                     } else if seq[idx] == '*' {
-                        return acc * *x;
+                        acc * *x
                     } else {
                         panic!("Invalid operator: {}", seq[idx]);
                     }
@@ -76,6 +63,10 @@ fn generate_possible_operator_sequences(
     number_of_operators_in_equation: usize,
 ) -> HashSet<Vec<char>> {
     let mut result = HashSet::new();
+    if number_of_operators_in_equation == 0 {
+        result.insert(Vec::new());
+        return result;
+    }
     let signs = ['+', '*'];
     let op = 2_u32.pow(number_of_operators_in_equation as u32);
 
@@ -99,7 +90,7 @@ fn generate_possible_operator_sequences(
     result
 }
 
-fn puzzle_2(assertions: Vec<Assertion>) -> u64 {
+pub fn puzzle_2(assertions: &[Assertion]) -> u64 {
     let mut sum = 0;
     for assertion in assertions {
         let op_seq = generate_possible_operator_sequences_improved(assertion.operands.len() - 1);
@@ -108,11 +99,11 @@ fn puzzle_2(assertions: Vec<Assertion>) -> u64 {
                 assertion.operands[0],
                 |acc, (idx, x)| {
                     if seq[idx] == '+' {
-                        return acc + *x;
+                        acc + *x
                     } else if seq[idx] == '*' {
-                        return acc * *x;
+                        acc * *x
                     } else if seq[idx] == '|' {
-                        return (acc.to_string() + &x.to_string()).parse::<u64>().unwrap();
+                        (acc.to_string() + &x.to_string()).parse::<u64>().unwrap()
                     } else {
                         panic!("Invalid operator: {}", seq[idx]);
                     }
@@ -131,6 +122,10 @@ fn generate_possible_operator_sequences_improved(
     number_of_operators_in_equation: usize,
 ) -> HashSet<Vec<char>> {
     let mut result = HashSet::new();
+    if number_of_operators_in_equation == 0 {
+        result.insert(Vec::new());
+        return result;
+    }
     let signs = ['+', '*', '|'];
     let op = 3_u32.pow(number_of_operators_in_equation as u32);
 
@@ -155,14 +150,34 @@ fn generate_possible_operator_sequences_improved(
     result
 }
 
+pub struct DaySeven;
+
+impl Solution for DaySeven {
+    const DAY: u8 = 7;
+    type Input = Vec<Assertion>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_assertions(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        generate_possible_operator_sequences, get_file_path, parse_input_to_assertions, puzzle_1,
-        puzzle_2,
+        generate_possible_operator_sequences, parse_input_to_assertions, puzzle_1, puzzle_2,
     };
     use std::collections::HashSet;
 
+    aoc_common::test_answers!(crate::DaySeven);
+
     #[test]
     fn it_returns_4_operation_sequences() {
         let result = generate_possible_operator_sequences(2);
@@ -215,34 +230,20 @@ mod tests {
     }
 
     #[test]
-    fn it_returns_3749() {
-        let input_path = get_file_path("test-input.txt".to_string());
-        let assertions = parse_input_to_assertions(input_path);
-        let result = puzzle_1(assertions);
-        assert_eq!(result, 3749);
-    }
-
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let input_path = get_file_path("input.txt".to_string());
-        let assertions = parse_input_to_assertions(input_path);
-        let result = puzzle_1(assertions);
-        assert_eq!(result, 1611660863222);
+    fn it_reports_the_position_of_a_malformed_operand() {
+        let err = parse_input_to_assertions("190: 10 19\n3267: 81 4O 27").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 10, "4O"));
     }
 
     #[test]
-    fn it_returns_11387() {
-        let input_path = get_file_path("test-input.txt".to_string());
-        let assertions = parse_input_to_assertions(input_path);
-        let result = puzzle_2(assertions);
-        assert_eq!(result, 11387);
+    fn it_rejects_an_equation_without_operands() {
+        let err = parse_input_to_assertions("190: 10 19\n5:").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
     }
 
     #[test]
-    fn it_returns_puzzle_2_score() {
-        let input_path = get_file_path("input.txt".to_string());
-        let assertions = parse_input_to_assertions(input_path);
-        let result = puzzle_2(assertions);
-        assert_eq!(result, 945341732469724);
+    fn it_checks_a_single_operand_against_the_result() {
+        let data = parse_input_to_assertions("5: 5\n6: 7").unwrap();
+        assert_eq!((puzzle_1(&data), puzzle_2(&data)), (5, 5));
     }
 }
@@ -0,0 +1,274 @@
+use crate::{Register, TraceEntry, Vm};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// Instructions run by `continue` before giving up on a program that never
+/// reaches a breakpoint, a watch or the end.
+pub const CONTINUE_LIMIT: usize = 1_000_000;
+
+const HELP: &str = "\
+step [n] (s)      run n instructions, 1 by default, printing each
+continue (c)      run until a breakpoint, a watched change or the end
+break <ptr> (b)   stop before the instruction at ptr
+delete <ptr> (d)  remove the breakpoint at ptr
+watch <reg> (w)   stop after register A, B or C changes
+registers (r)     print the registers and the pointer
+output (o)        print everything out produced so far
+quit (q)          leave the debugger";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Step(usize),
+    Continue,
+    Break(usize),
+    Delete(usize),
+    Watch(Register),
+    Registers,
+    Output,
+    Help,
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Command, String> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or("step");
+        let argument = words.next();
+        let pointer = || {
+            argument
+                .ok_or_else(|| format!("`{name}` needs an instruction pointer"))?
+                .parse::<usize>()
+                .map_err(|err| format!("invalid pointer ({err})"))
+        };
+        let command = match name {
+            "step" | "s" => match argument {
+                Some(count) => Command::Step(
+                    count
+                        .parse()
+                        .map_err(|err| format!("invalid step count ({err})"))?,
+                ),
+                None => Command::Step(1),
+            },
+            "continue" | "c" => Command::Continue,
+            "break" | "b" => Command::Break(pointer()?),
+            "delete" | "d" => Command::Delete(pointer()?),
+            "watch" | "w" => Command::Watch(parse_register(argument)?),
+            "registers" | "r" => Command::Registers,
+            "output" | "o" => Command::Output,
+            "help" | "h" => Command::Help,
+            "quit" | "q" => Command::Quit,
+            _ => return Err(format!("unknown command `{name}`, try `help`")),
+        };
+        Ok(command)
+    }
+}
+
+fn parse_register(argument: Option<&str>) -> Result<Register, String> {
+    match argument {
+        Some("a" | "A") => Ok(Register::A),
+        Some("b" | "B") => Ok(Register::B),
+        Some("c" | "C") => Ok(Register::C),
+        _ => Err("`watch` needs register A, B or C".to_string()),
+    }
+}
+
+/// Why the debugger handed control back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    Breakpoint(usize),
+    Watch {
+        register: Register,
+        from: u64,
+        to: u64,
+    },
+    Limit,
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Halted => write!(f, "halted"),
+            Stop::Breakpoint(pointer) => write!(f, "breakpoint at {pointer}"),
+            Stop::Watch { register, from, to } => write!(f, "{register} changed {from} -> {to}"),
+            Stop::Limit => write!(f, "no stop after {CONTINUE_LIMIT} instructions"),
+        }
+    }
+}
+
+/// A [`Vm`] with breakpoints on instruction pointers and watched registers.
+pub struct Debugger<'a> {
+    pub vm: Vm<'a>,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<Register>,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(vm: Vm<'a>) -> Self {
+        Debugger {
+            vm,
+            breakpoints: BTreeSet::new(),
+            watches: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, pointer: usize) {
+        self.breakpoints.insert(pointer);
+    }
+
+    pub fn remove_breakpoint(&mut self, pointer: usize) -> bool {
+        self.breakpoints.remove(&pointer)
+    }
+
+    pub fn watch(&mut self, register: Register) {
+        self.watches.insert(register);
+    }
+
+    /// Runs at least one instruction, then keeps going until the program
+    /// halts, reaches a breakpoint or changes a watched register.
+    pub fn resume(&mut self) -> (Option<TraceEntry>, Stop) {
+        let mut last = None;
+        for _ in 0..CONTINUE_LIMIT {
+            let Some(entry) = self.vm.step() else {
+                return (last, Stop::Halted);
+            };
+            let changed = self.watches.iter().find_map(|&register| {
+                let (from, to) = (entry.before.get(register), entry.after.get(register));
+                (from != to).then_some(Stop::Watch { register, from, to })
+            });
+            last = Some(entry);
+            if let Some(stop) = changed {
+                return (last, stop);
+            }
+            if self.vm.is_halted() {
+                return (last, Stop::Halted);
+            }
+            if self.breakpoints.contains(&self.vm.pointer) {
+                return (last, Stop::Breakpoint(self.vm.pointer));
+            }
+        }
+        (last, Stop::Limit)
+    }
+}
+
+/// Reads commands from `input` until `quit` or the end of input, reporting
+/// on `out`.
+pub fn run_session(
+    debugger: &mut Debugger,
+    input: impl BufRead,
+    mut out: impl Write,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}  pointer={}",
+        debugger.vm.registers, debugger.vm.pointer
+    )?;
+    write!(out, "> ")?;
+    out.flush()?;
+    for line in input.lines() {
+        match Command::parse(&line?) {
+            Ok(Command::Step(count)) => {
+                for _ in 0..count {
+                    match debugger.vm.step() {
+                        Some(entry) => writeln!(out, "{entry}")?,
+                        None => {
+                            writeln!(out, "{}", Stop::Halted)?;
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(Command::Continue) => {
+                let (last, stop) = debugger.resume();
+                if let Some(entry) = last {
+                    writeln!(out, "{entry}")?;
+                }
+                writeln!(out, "{stop}")?;
+            }
+            Ok(Command::Break(pointer)) => {
+                debugger.add_breakpoint(pointer);
+                writeln!(out, "breakpoint at {pointer}")?;
+            }
+            Ok(Command::Delete(pointer)) => {
+                if !debugger.remove_breakpoint(pointer) {
+                    writeln!(out, "no breakpoint at {pointer}")?;
+                }
+            }
+            Ok(Command::Watch(register)) => {
+                debugger.watch(register);
+                writeln!(out, "watching {register}")?;
+            }
+            Ok(Command::Registers) => {
+                writeln!(
+                    out,
+                    "{}  pointer={}",
+                    debugger.vm.registers, debugger.vm.pointer
+                )?;
+            }
+            Ok(Command::Output) => {
+                writeln!(out, "{}", crate::format_output(&debugger.vm.output))?;
+            }
+            Ok(Command::Help) => writeln!(out, "{HELP}")?,
+            Ok(Command::Quit) => return Ok(()),
+            Err(err) => writeln!(out, "{err}")?,
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debugger::{run_session, Command, Debugger, Stop};
+    use crate::{Register, Registers, Vm};
+
+    const PROGRAM: [u8; 6] = [0, 1, 5, 4, 3, 0];
+
+    fn debugger() -> Debugger<'static> {
+        let registers = Registers { a: 729, b: 0, c: 0 };
+        Debugger::new(Vm::new(&PROGRAM, registers))
+    }
+
+    #[test]
+    fn it_parses_commands_and_abbreviations() {
+        assert_eq!(Command::parse(""), Ok(Command::Step(1)));
+        assert_eq!(Command::parse("s 5"), Ok(Command::Step(5)));
+        assert_eq!(Command::parse("break 4"), Ok(Command::Break(4)));
+        assert_eq!(Command::parse("w a"), Ok(Command::Watch(Register::A)));
+        assert!(Command::parse("break").is_err());
+        assert!(Command::parse("jump 3").is_err());
+    }
+
+    #[test]
+    fn it_stops_at_breakpoints_and_watches() {
+        let mut debugger = debugger();
+        debugger.add_breakpoint(4);
+        assert_eq!(debugger.resume().1, Stop::Breakpoint(4));
+        assert_eq!(debugger.vm.output, vec![4]);
+        debugger.remove_breakpoint(4);
+        debugger.watch(Register::A);
+        let (last, stop) = debugger.resume();
+        assert_eq!(last.unwrap().pointer, 0);
+        assert_eq!(
+            stop,
+            Stop::Watch {
+                register: Register::A,
+                from: 364,
+                to: 182
+            }
+        );
+    }
+
+    #[test]
+    fn it_runs_a_scripted_session() {
+        let mut debugger = debugger();
+        let mut out = Vec::new();
+        run_session(&mut debugger, "s 2\nc\no\nq\nc\n".as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("   2: out 4 (=364)"));
+        assert!(out.contains("halted"));
+        assert!(out.contains("4,6,3,5,6,3,5,2,1,0"));
+    }
+}
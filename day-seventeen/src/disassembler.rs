@@ -0,0 +1,142 @@
+use crate::Opcode;
+use std::fmt;
+
+/// One decoded instruction at its position in the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub pointer: usize,
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// The operand as the instruction reads it: combo operands name their
+    /// register, literal operands stay numbers and `bxc` has none.
+    pub fn operand_name(&self) -> Option<String> {
+        match self.opcode {
+            Opcode::Bxc => None,
+            opcode if opcode.takes_combo() => Some(combo_name(self.operand).to_string()),
+            _ => Some(self.operand.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        if let Some(operand) = self.operand_name() {
+            write!(f, " {operand}")?;
+        }
+        Ok(())
+    }
+}
+
+fn combo_name(operand: u8) -> &'static str {
+    ["0", "1", "2", "3", "A", "B", "C", "reserved"][operand as usize]
+}
+
+/// Decodes `program` two numbers at a time; a trailing odd number is not an
+/// instruction and is left out.
+pub fn disassemble(program: &[u8]) -> Vec<Instruction> {
+    program
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(index, pair)| Instruction {
+            pointer: index * 2,
+            opcode: Opcode::decode(pair[0]),
+            operand: pair[1],
+        })
+        .collect()
+}
+
+/// One instruction per line, prefixed by its pointer.
+pub fn listing(program: &[u8]) -> String {
+    disassemble(program)
+        .iter()
+        .map(|instruction| format!("{:>4}: {instruction}\n", instruction.pointer))
+        .collect()
+}
+
+/// Pseudo-code for the usual puzzle shape: a straight-line body closed by a
+/// single `jnz 0`. Returns `None` for anything else.
+pub fn decompile(program: &[u8]) -> Option<String> {
+    let instructions = disassemble(program);
+    let (last, body) = instructions.split_last()?;
+    if last.opcode != Opcode::Jnz || last.operand != 0 || !program.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut code = String::from("do {\n");
+    for instruction in body {
+        code.push_str(&format!("    {};\n", statement(instruction)?));
+    }
+    code.push_str("} while a != 0\n");
+    Some(code)
+}
+
+fn statement(instruction: &Instruction) -> Option<String> {
+    let literal = instruction.operand;
+    // Only combo opcodes read the operand through the combo table, where 7 is
+    // reserved; elsewhere it is a plain literal.
+    let combo = if instruction.opcode.takes_combo() {
+        match literal {
+            0..=3 => literal.to_string(),
+            4 => "a".to_string(),
+            5 => "b".to_string(),
+            6 => "c".to_string(),
+            _ => return None,
+        }
+    } else {
+        String::new()
+    };
+    let statement = match instruction.opcode {
+        Opcode::Adv => format!("a >>= {combo}"),
+        Opcode::Bxl => format!("b ^= {literal}"),
+        Opcode::Bst if literal <= 3 => format!("b = {combo}"),
+        Opcode::Bst => format!("b = {combo} % 8"),
+        Opcode::Jnz => return None,
+        Opcode::Bxc => "b ^= c".to_string(),
+        Opcode::Out if literal <= 3 => format!("out({combo})"),
+        Opcode::Out => format!("out({combo} % 8)"),
+        Opcode::Bdv => format!("b = a >> {combo}"),
+        Opcode::Cdv => format!("c = a >> {combo}"),
+    };
+    Some(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::disassembler::{decompile, listing};
+
+    #[test]
+    fn it_names_combo_operands() {
+        assert_eq!(
+            listing(&[0, 1, 5, 4, 3, 0, 4, 7]),
+            "   0: adv 1\n   2: out A\n   4: jnz 0\n   6: bxc\n"
+        );
+    }
+
+    #[test]
+    fn it_decompiles_a_single_loop() {
+        let program = [2, 4, 1, 1, 7, 5, 4, 0, 0, 3, 5, 5, 3, 0];
+        assert_eq!(
+            decompile(&program).unwrap(),
+            "do {\n    b = a % 8;\n    b ^= 1;\n    c = a >> b;\n    b ^= c;\n    a >>= 3;\n    out(b % 8);\n} while a != 0\n"
+        );
+    }
+
+    #[test]
+    fn it_decompiles_literal_sevens() {
+        let program = [2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
+        assert_eq!(
+            decompile(&program).unwrap(),
+            "do {\n    b = a % 8;\n    b ^= 7;\n    c = a >> b;\n    a >>= 3;\n    b ^= c;\n    b ^= 7;\n    out(b % 8);\n} while a != 0\n"
+        );
+        assert_eq!(decompile(&[5, 7, 3, 0]), None);
+    }
+
+    #[test]
+    fn it_leaves_other_shapes_alone() {
+        assert_eq!(decompile(&[0, 1, 3, 0, 5, 4]), None);
+        assert_eq!(decompile(&[0, 1, 3, 2]), None);
+    }
+}
@@ -1,273 +1,481 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
+pub mod debugger;
+pub mod disassembler;
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
+use std::fmt;
 
-struct Register {
-    kind: RegisterKind,
-    value: i32,
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
 }
 
-#[derive(Eq, PartialEq)]
-enum RegisterKind {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Register {
     A,
     B,
     C,
 }
 
-type Registers = Vec<Register>;
+impl Registers {
+    pub fn get(&self, register: Register) -> u64 {
+        match register {
+            Register::A => self.a,
+            Register::B => self.b,
+            Register::C => self.c,
+        }
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A={} B={} C={}", self.a, self.b, self.c)
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::A => "A",
+            Register::B => "B",
+            Register::C => "C",
+        };
+        f.write_str(name)
+    }
+}
 
-type Program = Vec<i32>;
+pub type Program = Vec<u8>;
 
-fn parse_input(filename: PathBuf) -> (Registers, Program) {
+pub fn parse_input(input: &str) -> Result<(Registers, Program), ParseError> {
     let mut parse_program = false;
-    let mut registers = Vec::new();
-    let mut program = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                if line.is_empty() {
-                    parse_program = true
-                }
-                if parse_program {
-                    let prog_seq = line.split(": ").last().unwrap();
-                    program = prog_seq
-                        .split(",")
-                        .map(|s| {
-                            s.to_string()
-                                .parse::<i32>()
-                                .expect("Could not parse program value")
-                        })
-                        .collect::<Vec<i32>>();
-                } else {
-                    let split = line
-                        .split(": ")
-                        .map(|s| s.to_string())
-                        .collect::<Vec<String>>();
-                    let reg_value = split[1]
-                        .to_string()
-                        .parse::<i32>()
-                        .expect("Could not parse register value");
-                    let reg = match split[0].chars().rev().take(1).collect::<String>().as_str() {
-                        "A" => Register {
-                            kind: RegisterKind::A,
-                            value: reg_value,
-                        },
-                        "B" => Register {
-                            kind: RegisterKind::B,
-                            value: reg_value,
-                        },
-                        "c" => Register {
-                            kind: RegisterKind::C,
-                            value: reg_value,
-                        },
-                        _ => panic!("Unknown register name"),
-                    };
-                    registers.push(reg);
+    let (mut a, mut b, mut c) = (None, None, None);
+    let mut program = None;
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            parse_program = true;
+            continue;
+        }
+        if parse_program {
+            let (name, prog_seq) = split_once(i, line, ": ")?;
+            if name != "Program" {
+                return Err(ParseError::at(i, line, name, "expected `Program`"));
+            }
+            let instructions = prog_seq
+                .split(",")
+                .map(|s| match parse_number::<u8>(i, line, s)? {
+                    value @ 0..=7 => Ok(value),
+                    _ => Err(ParseError::at(i, line, s, "expected a 3-bit number")),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            let operands = prog_seq.split(",").skip(1).step_by(2);
+            for (instruction, operand) in instructions.chunks_exact(2).zip(operands) {
+                if Opcode::decode(instruction[0]).takes_combo() && instruction[1] == 7 {
+                    return Err(ParseError::at(
+                        i,
+                        line,
+                        operand,
+                        "combo operand 7 is reserved",
+                    ));
                 }
             }
+            program = Some(instructions);
+        } else {
+            let (name, value) = split_once(i, line, ": ")?;
+            let register = match name {
+                "Register A" => &mut a,
+                "Register B" => &mut b,
+                "Register C" => &mut c,
+                _ => return Err(ParseError::at(i, line, name, "unknown register name")),
+            };
+            *register = Some(parse_number(i, line, value)?);
         }
     }
-    (registers, program)
+    let missing = |what: &str| ParseError::end_of_input(input, format!("{what} not found"));
+    let registers = Registers {
+        a: a.ok_or_else(|| missing("register A"))?,
+        b: b.ok_or_else(|| missing("register B"))?,
+        c: c.ok_or_else(|| missing("register C"))?,
+    };
+    let program = program.ok_or_else(|| missing("program"))?;
+    Ok((registers, program))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
 }
 
-fn read_next_instruction(
-    pointer: usize,
-    program: Program,
-    registers: &mut Registers,
-    outputs: &mut Vec<i32>,
-) {
-    if pointer >= program.len() || pointer + 1 > program.len() {
-        return;
+impl Opcode {
+    pub fn decode(value: u8) -> Opcode {
+        match value {
+            0 => Opcode::Adv,
+            1 => Opcode::Bxl,
+            2 => Opcode::Bst,
+            3 => Opcode::Jnz,
+            4 => Opcode::Bxc,
+            5 => Opcode::Out,
+            6 => Opcode::Bdv,
+            7 => Opcode::Cdv,
+            _ => panic!("Unknown opcode: {value}"),
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
     }
-    let mut next_pointer = pointer + 2;
-    let opcode = program[pointer];
-    match opcode {
-        0 => compute_adv(program[pointer + 1], registers),
-        1 => compute_bxl(opcode, registers),
-        2 => compute_bst(program[pointer + 1], registers),
-        3 => compute_jnz(opcode, next_pointer, registers),
-        4 => compute_bxc(registers),
-        5 => compute_out(program[pointer + 1], outputs),
-        6 => compute_bdv(program[pointer + 1], registers),
-        7 => compute_cdv(program[pointer + 1], registers),
-        _ => panic!("Unknown opcode: {}", opcode),
+
+    /// Whether the operand names a literal or register through the combo table.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
     }
-    read_next_instruction(next_pointer, program, registers, outputs)
 }
 
-fn puzzle_1(mut data: (Registers, Program)) -> String {
-    let (mut registers, program) = data;
-    let mut outputs = Vec::new();
-    read_next_instruction(0, program, &mut registers, &mut outputs);
-    println!("{outputs:?}");
-    "0".to_string()
+/// One executed instruction, with its operand resolved and the registers
+/// around it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub pointer: usize,
+    pub opcode: Opcode,
+    pub operand: u8,
+    pub resolved: u64,
+    pub before: Registers,
+    pub after: Registers,
+    pub output: Option<u8>,
 }
 
-fn get_combo_op_value(combo_op: i32, registers: &Registers) -> i32 {
-    match combo_op {
-        0..3 => combo_op,
-        4 => {
-            registers
-                .iter()
-                .find(|r| r.kind == RegisterKind::A)
-                .unwrap()
-                .value
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>4}: {} {}",
+            self.pointer,
+            self.opcode.mnemonic(),
+            self.operand
+        )?;
+        if self.opcode.takes_combo() && self.operand >= 4 {
+            write!(f, " (={})", self.resolved)?;
         }
-        5 => {
-            registers
-                .iter()
-                .find(|r| r.kind == RegisterKind::B)
-                .unwrap()
-                .value
+        write!(f, " | {} -> {}", self.before, self.after)?;
+        if let Some(value) = self.output {
+            write!(f, " | out {value}")?;
         }
-        6 => {
-            registers
-                .iter()
-                .find(|r| r.kind == RegisterKind::C)
-                .unwrap()
-                .value
-        }
-        _ => panic!("Unknown operation: {}", combo_op),
+        Ok(())
     }
 }
 
-fn compute_adv(combo_op: i32, registers: &mut Registers) {
-    let n = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::A)
-        .unwrap()
-        .value;
-    let power = get_combo_op_value(combo_op, &registers);
-    let d = 2i32.pow(power as u32);
-    let res = n as u32 / d as u32;
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::A) {
-        Some(reg) => {
-            reg.value = res as i32;
+/// The 3-bit computer: registers, an instruction pointer and what `out` printed.
+#[derive(Clone, Debug)]
+pub struct Vm<'a> {
+    program: &'a [u8],
+    pub registers: Registers,
+    pub pointer: usize,
+    pub output: Vec<u8>,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a [u8], registers: Registers) -> Self {
+        Vm {
+            program,
+            registers,
+            pointer: 0,
+            output: Vec::new(),
         }
-        None => {}
     }
-}
-fn compute_bxl(literal_op: i32, registers: &mut Registers) {
-    let b = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::B)
-        .unwrap()
-        .value;
-    let res = b ^ literal_op;
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::B) {
-        Some(reg) => {
-            reg.value = res as i32;
+
+    /// The computer halts once the pointer no longer reaches a whole instruction.
+    pub fn is_halted(&self) -> bool {
+        self.pointer + 1 >= self.program.len()
+    }
+
+    fn combo(&self, operand: u8) -> Option<u64> {
+        match operand {
+            0..=3 => Some(operand as u64),
+            4 => Some(self.registers.a),
+            5 => Some(self.registers.b),
+            6 => Some(self.registers.c),
+            _ => None,
         }
-        None => {}
     }
-}
-fn compute_bst(combo_op: i32, registers: &mut Registers) {
-    let rest = combo_op % 8;
-    let bits = format!("{:b}", rest);
-    let mut c_bits = bits.chars().collect::<Vec<char>>();
-    c_bits.sort();
-    let res = c_bits
-        .iter()
-        .take(3)
-        .map(|c| c.to_digit(10).unwrap() as i32)
-        .sum::<i32>();
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::B) {
-        Some(reg) => {
-            reg.value = res;
+
+    fn divide(&self, shift: u64) -> u64 {
+        self.registers
+            .a
+            .checked_shr(shift.try_into().unwrap_or(u32::MAX))
+            .unwrap_or(0)
+    }
+
+    pub fn program(&self) -> &'a [u8] {
+        self.program
+    }
+
+    /// Executes one instruction and describes it, or returns `None` when
+    /// already halted. A jump into the middle of an instruction can still
+    /// reach the reserved combo operand 7, which halts the computer.
+    pub fn step(&mut self) -> Option<TraceEntry> {
+        if self.is_halted() {
+            return None;
+        }
+        let pointer = self.pointer;
+        let before = self.registers;
+        let opcode = Opcode::decode(self.program[pointer]);
+        let operand = self.program[pointer + 1];
+        let resolved = match opcode {
+            Opcode::Bxc => 0,
+            _ if opcode.takes_combo() => match self.combo(operand) {
+                Some(value) => value,
+                None => {
+                    self.pointer = self.program.len();
+                    return None;
+                }
+            },
+            _ => operand as u64,
+        };
+        let printed = self.output.len();
+        let mut next_pointer = pointer + 2;
+        match opcode {
+            Opcode::Adv => self.registers.a = self.divide(resolved),
+            Opcode::Bxl => self.registers.b ^= operand as u64,
+            Opcode::Bst => self.registers.b = resolved % 8,
+            Opcode::Jnz => {
+                if self.registers.a != 0 {
+                    next_pointer = operand as usize;
+                }
+            }
+            Opcode::Bxc => self.registers.b ^= self.registers.c,
+            Opcode::Out => self.output.push((resolved % 8) as u8),
+            Opcode::Bdv => self.registers.b = self.divide(resolved),
+            Opcode::Cdv => self.registers.c = self.divide(resolved),
         }
-        None => {}
+        self.pointer = next_pointer;
+        Some(TraceEntry {
+            pointer,
+            opcode,
+            operand,
+            resolved,
+            before,
+            after: self.registers,
+            output: self.output.get(printed).copied(),
+        })
     }
-}
-fn compute_jnz(literal_op: i32, mut next_pointer: usize, registers: &mut Registers) {
-    let reg_a = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::A)
-        .unwrap()
-        .value;
-    if reg_a != 0 {
-        next_pointer = literal_op as usize;
+
+    pub fn run(&mut self) -> &[u8] {
+        while self.step().is_some() {}
+        &self.output
+    }
+
+    /// Runs until halting or for at most `limit` instructions, recording
+    /// each one so loops can be inspected.
+    pub fn trace(&mut self, limit: usize) -> Vec<TraceEntry> {
+        std::iter::from_fn(|| self.step()).take(limit).collect()
     }
 }
-fn compute_bxc(registers: &mut Registers) {
-    let b = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::B)
-        .unwrap()
-        .value;
-    let c = registers
+
+pub fn format_output(output: &[u8]) -> String {
+    output
         .iter()
-        .find(|r| r.kind == RegisterKind::C)
-        .unwrap()
-        .value;
-    let res = b ^ c;
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::B) {
-        Some(reg) => {
-            reg.value = res as i32;
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn puzzle_1(data: &(Registers, Program)) -> String {
+    let (registers, program) = data;
+    let mut vm = Vm::new(program, *registers);
+    format_output(vm.run())
+}
+
+/// Instructions a candidate run may take per program number before it is
+/// treated as stuck in a loop.
+const QUINE_STEP_BUDGET: usize = 10_000;
+
+/// Whether starting with register A set to `a` makes the program print
+/// exactly `expected`, stopping as soon as the output diverges.
+fn prints(program: &[u8], registers: Registers, a: u64, expected: &[u8]) -> bool {
+    let mut vm = Vm::new(program, Registers { a, ..registers });
+    for _ in 0..QUINE_STEP_BUDGET * program.len().max(1) {
+        if vm.step().is_none() {
+            return vm.output == expected;
+        }
+        if !expected.starts_with(&vm.output) {
+            return false;
         }
-        None => {}
     }
+    false
 }
-fn compute_out(combo_op: i32, outputs: &mut Vec<i32>) {
-    outputs.push(combo_op % 8);
+
+fn extend_quine(program: &[u8], registers: Registers, a: u64, printed: usize) -> Option<u64> {
+    if printed == program.len() {
+        return Some(a);
+    }
+    let tail = &program[program.len() - printed - 1..];
+    (0..8)
+        .map(|bits| (a << 3) | bits)
+        .filter(|&candidate| prints(program, registers, candidate, tail))
+        .find_map(|candidate| extend_quine(program, registers, candidate, printed + 1))
 }
-fn compute_bdv(combo_op: i32, registers: &mut Registers) {
-    let n = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::A)
-        .unwrap()
-        .value;
-    let power = get_combo_op_value(combo_op, &registers);
-    let d = 2i32.pow(power as u32);
-    let res = n as u32 / d as u32;
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::B) {
-        Some(reg) => {
-            reg.value = res as i32;
-        }
-        None => {}
+
+/// Lowest register A value for which the program prints itself, or `None`
+/// when there is none.
+///
+/// Puzzle programs consume register A three bits per printed number, so the
+/// value is built from the last printed number backwards: every candidate
+/// is extended by each 3-bit group and kept only if the VM then prints the
+/// matching tail of the program. Trying groups in ascending order makes the
+/// first complete match the lowest.
+pub fn puzzle_2(data: &(Registers, Program)) -> Option<u64> {
+    let (registers, program) = data;
+    if program.is_empty() {
+        return None;
     }
+    extend_quine(program, *registers, 0, 0)
 }
-fn compute_cdv(combo_op: i32, registers: &mut Registers) {
-    let n = registers
-        .iter()
-        .find(|r| r.kind == RegisterKind::A)
-        .unwrap()
-        .value;
-    let power = get_combo_op_value(combo_op, &registers);
-    let d = 2i32.pow(power as u32);
-    let res = n as u32 / d as u32;
-    match registers.iter_mut().find(|r| r.kind == RegisterKind::C) {
-        Some(reg) => {
-            reg.value = res as i32;
+
+pub struct DaySeventeen;
+
+impl Solution for DaySeventeen {
+    const DAY: u8 = 17;
+    type Input = (Registers, Program);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        match puzzle_2(input) {
+            Some(a) => a.into(),
+            None => {
+                Answer::NoAnswer("no register A value makes the program print itself".to_string())
+            }
         }
-        None => {}
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_2, DaySeventeen, Registers, Vm};
+    use aoc_common::solution::{Answer, Solution};
+
+    aoc_common::test_answers!(crate::DaySeventeen);
+
+    #[test]
+    fn it_reports_programs_that_cannot_print_themselves() {
+        let registers = Registers::default();
+        assert_eq!(puzzle_2(&(registers, vec![5, 0, 3, 0])), None);
+        assert_eq!(puzzle_2(&(registers, vec![])), None);
+        assert!(matches!(
+            DaySeventeen.part_two(&(registers, vec![5, 0, 3, 0])),
+            Answer::NoAnswer(_)
+        ));
+    }
+
+    #[test]
+    fn it_parses_all_three_registers() {
+        let input = include_str!("../assets/test-input.txt");
+        let (registers, program) = parse_input(input).unwrap();
+        assert_eq!(registers, Registers { a: 729, b: 0, c: 0 });
+        assert_eq!(program, vec![0, 1, 5, 4, 3, 0]);
+    }
+
+    #[test]
+    fn it_reports_a_malformed_register_value() {
+        let err = parse_input("Register A: -x").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 13, "-x"));
+    }
+
+    #[test]
+    fn it_runs_the_small_examples() {
+        let registers = |a, b, c| Registers { a, b, c };
+        let mut vm = Vm::new(&[2, 6], registers(0, 0, 9));
+        vm.run();
+        assert_eq!(vm.registers.b, 1);
+        let mut vm = Vm::new(&[5, 0, 5, 1, 5, 4], registers(10, 0, 0));
+        assert_eq!(vm.run(), &[0, 1, 2]);
+        let mut vm = Vm::new(&[0, 1, 5, 4, 3, 0], registers(2024, 0, 0));
+        assert_eq!(vm.run(), &[4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(vm.registers.a, 0);
+        let mut vm = Vm::new(&[1, 7], registers(0, 29, 0));
+        vm.run();
+        assert_eq!(vm.registers.b, 26);
+        let mut vm = Vm::new(&[4, 0], registers(0, 2024, 43690));
+        vm.run();
+        assert_eq!(vm.registers.b, 44354);
+    }
+
+    #[test]
+    fn it_traces_each_instruction() {
+        let (registers, program) = parse_input(include_str!("../assets/test-input.txt")).unwrap();
+        let mut vm = Vm::new(&program, registers);
+        let trace = vm.trace(4);
+        assert_eq!(trace.len(), 4);
+        assert_eq!(
+            trace[0].to_string(),
+            "   0: adv 1 | A=729 B=0 C=0 -> A=364 B=0 C=0"
+        );
+        assert_eq!(
+            trace[1].to_string(),
+            "   2: out 4 (=364) | A=364 B=0 C=0 -> A=364 B=0 C=0 | out 4"
+        );
+        assert_eq!(trace[2].pointer, 4);
+        assert_eq!(trace[3].pointer, 0);
+    }
+
+    #[test]
+    fn it_rejects_opcodes_wider_than_three_bits() {
+        let err = parse_input("Register A: 1\n\nProgram: 0,8").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 12));
+    }
+
+    #[test]
+    fn it_rejects_the_reserved_combo_operand() {
+        let err = parse_input("Register A: 1\n\nProgram: 0,7,5,4,3,0").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 12, "7"));
+        // As a literal operand, 7 is fine.
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,4,3,0";
+        assert!(parse_input(input).is_ok());
+    }
+
+    #[test]
+    fn it_reports_missing_registers_and_program() {
+        let err = parse_input("Register A: 1\nRegister C: 0\n\nProgram: 0,1").unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (5, "register B not found")
+        );
+        let err = parse_input("Register A: 1\nRegister B: 0\nRegister C: 0\n").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (4, "program not found"));
+    }
 
     #[test]
-    fn it_returns_4635635210() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let parsed = parse_input(file_path);
-        let result = puzzle_1(parsed);
-        assert_eq!(result, "4635635210".to_string());
+    fn it_halts_on_a_reserved_operand_reached_by_a_jump() {
+        let program = [3, 3, 0, 5, 7, 0];
+        let mut vm = Vm::new(&program, Registers { a: 1, b: 0, c: 0 });
+        assert!(vm.step().is_some());
+        assert!(vm.step().is_none());
+        assert!(vm.is_halted());
     }
 }
@@ -0,0 +1,33 @@
+use aoc_common::read_asset;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_six::{parse_input_to_game_map, puzzle_2, puzzle_2_walking, JumpTable};
+use std::hint::black_box;
+
+/// Part two stepping cell by cell against jumping from turn to turn.
+fn walker(c: &mut Criterion) {
+    let input = read_asset!("input.txt");
+    let game_state = parse_input_to_game_map(&input).unwrap();
+    let mut group = c.benchmark_group("day 06 part two");
+    group.bench_function("walking", |b| {
+        b.iter(|| puzzle_2_walking(black_box(&game_state)))
+    });
+    group.bench_function("jump table", |b| {
+        b.iter(|| puzzle_2(black_box(&game_state)))
+    });
+    group.finish();
+}
+
+fn jump_table(c: &mut Criterion) {
+    let input = read_asset!("input.txt");
+    let game_state = parse_input_to_game_map(&input).unwrap();
+    c.bench_function("day 06 jump table build", |b| {
+        b.iter(|| JumpTable::new(black_box(game_state.map())))
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = walker, jump_table
+}
+criterion_main!(benches);
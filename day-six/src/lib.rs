@@ -1,232 +1,288 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-const MAX_LOOP_ITERATIONS: usize = 10000;
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn display_game_map(map: GameMap) {
-    let mut print_map = String::new();
-    for row in map {
-        let mut line = row
-            .iter()
-            .fold(String::new(), |acc, x| acc + &x.to_string());
-        line.push('\n');
-        print_map += &line;
-    }
-    println!("{}", print_map);
+use aoc_common::geometry::{Direction, Point};
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+#[derive(Clone)]
+pub struct GameState {
+    game_map: GameMap,
+    guard_position: Point,
+    direction: Direction,
 }
+pub type GameMap = Grid<char>;
 
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+pub fn parse_input_to_game_map(input: &str) -> Result<GameState, ParseError> {
+    let game_map = Grid::parse_with(input, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
+    let starting_pos = game_map
+        .find(&'^')
+        .ok_or_else(|| ParseError::end_of_input(input, "starting position `^` not found in map"))?;
+    Ok(GameState {
+        game_map,
+        guard_position: starting_pos,
+        direction: Direction::Up,
+    })
 }
 
-struct GameState {
-    game_map: GameMap,
-    guard_position: Position,
-    direction: Direction,
-    exited: bool,
-}
-type GameMap = Vec<Vec<char>>;
-
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-fn parse_input_to_game_map(input: PathBuf) -> GameState {
-    let mut game_map: GameMap = Vec::new();
-    let mut starting_pos: Position = Position { x: -1, y: -1 };
-    if let Ok(lines) = read_lines(input) {
-        for (y_idx, line) in lines.enumerate() {
-            if let Ok(line) = line {
-                let row = line
-                    .chars()
-                    .enumerate()
-                    .map(|(x_idx, c)| {
-                        if c == '^' {
-                            starting_pos = Position {
-                                x: x_idx as i32,
-                                y: y_idx as i32,
-                            };
-                        }
-                        c
-                    })
-                    .collect::<Vec<char>>();
-                game_map.push(row);
-            }
+impl GameState {
+    pub fn map(&self) -> &GameMap {
+        &self.game_map
+    }
+
+    /// The guard's patrol over the unchanged map.
+    pub fn walk(&self) -> Guard<'_> {
+        Guard {
+            map: &self.game_map,
+            obstacle: None,
+            state: Some((self.guard_position, self.direction)),
         }
     }
-    if starting_pos.x < 0 || starting_pos.y < 0 {
-        panic!("Starting position not found in map");
+
+    /// The guard's patrol with one extra obstruction at `obstacle`.
+    pub fn walk_with_obstacle(&self, obstacle: Point) -> Guard<'_> {
+        Guard {
+            obstacle: Some(obstacle),
+            ..self.walk()
+        }
     }
-    GameState {
-        game_map,
-        guard_position: starting_pos,
-        direction: Direction::Up,
-        exited: false,
+}
+
+/// The guard as a state machine: yields every `(position, direction)` from
+/// the start, turning right at obstructions, until the next step leaves the
+/// map. A looping patrol never ends.
+#[derive(Clone)]
+pub struct Guard<'a> {
+    map: &'a GameMap,
+    obstacle: Option<Point>,
+    state: Option<(Point, Direction)>,
+}
+
+impl Iterator for Guard<'_> {
+    type Item = (Point, Direction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, direction) = self.state?;
+        let next_pos = position + direction;
+        self.state = match self.map.get(next_pos) {
+            None => None,
+            Some('#') => Some((position, direction.turn_right())),
+            Some(_) if self.obstacle == Some(next_pos) => Some((position, direction.turn_right())),
+            Some(_) => Some((next_pos, direction)),
+        };
+        Some((position, direction))
     }
 }
 
-fn update_game_map(game_state: &mut GameState) {
-    let can_move_up = game_state.guard_position.y - 1 >= 0
-        && game_state.game_map[game_state.guard_position.y as usize - 1]
-            [game_state.guard_position.x as usize]
-            != '#';
-    let can_move_right = game_state.guard_position.x + 1 < game_state.game_map[0].len() as i32
-        && game_state.game_map[game_state.guard_position.y as usize]
-            [game_state.guard_position.x as usize + 1]
-            != '#';
-    let can_move_down = game_state.guard_position.y + 1 < game_state.game_map.len() as i32
-        && game_state.game_map[game_state.guard_position.y as usize + 1]
-            [game_state.guard_position.x as usize]
-            != '#';
-    let can_move_left = game_state.guard_position.x - 1 >= 0
-        && game_state.game_map[game_state.guard_position.y as usize]
-            [game_state.guard_position.x as usize - 1]
-            != '#';
-    match game_state.direction {
-        Direction::Up => {
-            if game_state.guard_position.y - 1 < 0 {
-                game_state.exited = true;
-            } else if can_move_up {
-                let next_pos = Position {
-                    x: game_state.guard_position.x,
-                    y: game_state.guard_position.y - 1,
-                };
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = 'X';
-                game_state.game_map[next_pos.y as usize][next_pos.x as usize] = '^';
-                game_state.guard_position = next_pos;
-            } else {
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = '>';
-                game_state.direction = Direction::Right;
-            }
+/// How a walk ends: off the map, or back in a position and direction the
+/// guard already had, which repeats forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Exited,
+    Looped,
+}
+
+/// Cuts `steps` short just before the first state that comes round again.
+pub fn until_repeat(
+    steps: impl Iterator<Item = (Point, Direction)>,
+) -> impl Iterator<Item = (Point, Direction)> {
+    let mut seen = HashSet::new();
+    steps.take_while(move |state| seen.insert(*state))
+}
+
+pub fn outcome(steps: impl Iterator<Item = (Point, Direction)>) -> Outcome {
+    let mut seen = HashSet::new();
+    for state in steps {
+        if !seen.insert(state) {
+            return Outcome::Looped;
         }
-        Direction::Right => {
-            if game_state.guard_position.x + 1 > game_state.game_map[0].len() as i32 - 1 {
-                game_state.exited = true;
-            } else if can_move_right {
-                let next_pos = Position {
-                    x: game_state.guard_position.x + 1,
-                    y: game_state.guard_position.y,
+    }
+    Outcome::Exited
+}
+
+pub fn visited_cells(steps: impl Iterator<Item = (Point, Direction)>) -> HashSet<Point> {
+    until_repeat(steps).map(|(position, _)| position).collect()
+}
+
+/// Draws the map with every cell the guard stood on marked `X`.
+pub fn render(map: &GameMap, steps: impl Iterator<Item = (Point, Direction)>) -> String {
+    let mut overlay = map.clone();
+    for (position, _) in until_repeat(steps) {
+        overlay[position] = 'X';
+    }
+    overlay.to_string()
+}
+
+pub fn puzzle_1(game_state: &GameState) -> usize {
+    visited_cells(game_state.walk()).len()
+}
+
+/// For every cell and heading, where the guard ends up before the next
+/// obstruction: `Some(stop)` where it has to turn, `None` when it walks off
+/// the map instead. Lets a simulation jump from turn to turn.
+pub struct JumpTable {
+    stops: Grid<[Option<Point>; 4]>,
+}
+
+impl JumpTable {
+    pub fn new(map: &GameMap) -> Self {
+        let mut stops = map.map(|_| [None; 4]);
+        for direction in Direction::ALL {
+            let step = direction.step();
+            // Cells further along `direction` are filled in first.
+            let mut points = map.points().collect::<Vec<_>>();
+            points.sort_by_key(|p| -(p.x * step.x + p.y * step.y));
+            for point in points {
+                let next = point + step;
+                stops[point][direction as usize] = match map.get(next) {
+                    None => None,
+                    Some('#') => Some(point),
+                    Some(_) => stops[next][direction as usize],
                 };
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = 'X';
-                game_state.game_map[next_pos.y as usize][next_pos.x as usize] = '>';
-                game_state.guard_position = next_pos;
-            } else {
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = 'v';
-                game_state.direction = Direction::Down;
             }
         }
-        Direction::Down => {
-            if game_state.guard_position.y + 1 > game_state.game_map.len() as i32 - 1 {
-                game_state.exited = true;
-            } else if can_move_down {
-                let next_pos = Position {
-                    x: game_state.guard_position.x,
-                    y: game_state.guard_position.y + 1,
-                };
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = 'X';
-                game_state.game_map[next_pos.y as usize][next_pos.x as usize] = 'v';
-                game_state.guard_position = next_pos;
-            } else {
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = '<';
-                game_state.direction = Direction::Left;
+        JumpTable { stops }
+    }
+
+    /// Where the guard heading `direction` from `position` has to turn, with
+    /// `obstacle` blocking on top of the map, or `None` if it leaves.
+    pub fn jump(
+        &self,
+        position: Point,
+        direction: Direction,
+        obstacle: Option<Point>,
+    ) -> Option<Point> {
+        let stop = self.stops[position][direction as usize];
+        let Some(obstacle) = obstacle else {
+            return stop;
+        };
+        let step = direction.step();
+        let offset = obstacle - position;
+        let distance = offset.x * step.x + offset.y * step.y;
+        let ahead = offset == step * distance && distance > 0;
+        let reach = match stop {
+            Some(stop) => {
+                let stop_offset = stop - position;
+                stop_offset.x * step.x + stop_offset.y * step.y
             }
+            None => i32::MAX,
+        };
+        if ahead && distance <= reach && self.stops.contains(obstacle) {
+            Some(obstacle - step)
+        } else {
+            stop
         }
-        Direction::Left => {
-            if game_state.guard_position.x - 1 < 0 {
-                game_state.exited = true;
-            } else if can_move_left {
-                let next_pos = Position {
-                    x: game_state.guard_position.x - 1,
-                    y: game_state.guard_position.y,
-                };
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = 'X';
-                game_state.game_map[next_pos.y as usize][next_pos.x as usize] = '<';
-                game_state.guard_position = next_pos;
-            } else {
-                game_state.game_map[game_state.guard_position.y as usize]
-                    [game_state.guard_position.x as usize] = '^';
-                game_state.direction = Direction::Up;
+    }
+
+    /// Like [`outcome`] over the guard's walk, but only visiting turns.
+    pub fn outcome(&self, start: Point, direction: Direction, obstacle: Option<Point>) -> Outcome {
+        let mut seen = HashSet::new();
+        let (mut position, mut direction) = (start, direction);
+        while let Some(stop) = self.jump(position, direction, obstacle) {
+            position = stop;
+            direction = direction.turn_right();
+            if !seen.insert((position, direction)) {
+                return Outcome::Looped;
             }
         }
+        Outcome::Exited
     }
 }
 
-fn puzzle_1(game_state: &mut GameState) -> i32 {
-    for i in 0..=MAX_LOOP_ITERATIONS {
-        if game_state.exited {
-            println!("Guard moved out of map");
-            break;
-        }
-        if i == MAX_LOOP_ITERATIONS {
-            println!("Maximum iterations reached");
-        }
-        update_game_map(game_state);
-    }
-    display_game_map(game_state.game_map.clone());
-    let count = game_state
-        .game_map
-        .iter()
-        .fold(String::new(), |acc, row| {
-            acc + &row.iter().collect::<String>()
+/// Counts the cells where one extra obstruction traps the guard in a loop.
+/// Only cells on the original route can change the walk, and the starting
+/// cell is off limits.
+pub fn puzzle_2(game_state: &GameState) -> usize {
+    let table = JumpTable::new(&game_state.game_map);
+    obstruction_candidates(game_state)
+        .filter(|point| {
+            table.outcome(
+                game_state.guard_position,
+                game_state.direction,
+                Some(*point),
+            ) == Outcome::Looped
         })
-        .chars()
-        .filter(|c| *c == 'X')
-        .count();
-    count as i32 + 1
+        .count()
+}
+
+/// [`puzzle_2`] stepping cell by cell through [`Guard`], kept to check and
+/// benchmark the jump table against.
+pub fn puzzle_2_walking(game_state: &GameState) -> usize {
+    obstruction_candidates(game_state)
+        .filter(|point| outcome(game_state.walk_with_obstacle(*point)) == Outcome::Looped)
+        .count()
+}
+
+fn obstruction_candidates(game_state: &GameState) -> impl Iterator<Item = Point> + '_ {
+    visited_cells(game_state.walk())
+        .into_iter()
+        .filter(|point| *point != game_state.guard_position)
+}
+
+pub struct DaySix;
+
+impl Solution for DaySix {
+    const DAY: u8 = 6;
+    type Input = GameState;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_game_map(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input_to_game_map, puzzle_1};
+    use crate::{
+        parse_input_to_game_map, puzzle_1, puzzle_2, puzzle_2_walking, render, JumpTable, Outcome,
+    };
+    use aoc_common::geometry::Direction;
+
+    aoc_common::test_answers!(crate::DaySix);
 
-    #[test]
-    fn it_returns_41() {
-        let input_path = get_file_path("test-input.txt".to_string());
-        let mut game_state = parse_input_to_game_map(input_path);
-        let result = puzzle_1(&mut game_state);
-        assert_eq!(result, 41);
-    }
     #[test]
     fn it_returns_max_loop_iterations() {
-        let input_path = get_file_path("test-loop-input.txt".to_string());
-        let mut game_state = parse_input_to_game_map(input_path);
-        let result = puzzle_1(&mut game_state);
+        let input = include_str!("../assets/test-loop-input.txt");
+        let game_state = parse_input_to_game_map(input).unwrap();
+        let result = puzzle_1(&game_state);
         assert_eq!(result, 20);
     }
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let input_path = get_file_path("input.txt".to_string());
-        let mut game_state = parse_input_to_game_map(input_path);
-        let result = puzzle_1(&mut game_state);
-        assert_eq!(result, 5129);
+    fn it_renders_the_route_without_touching_the_map() {
+        let input = include_str!("../assets/test-input.txt");
+        let game_state = parse_input_to_game_map(input).unwrap();
+        let rendered = render(&game_state.game_map, game_state.walk());
+        assert_eq!(rendered.matches('X').count(), 41);
+        assert_eq!(rendered.lines().nth(6), Some(".#XXXXXXX."));
+        assert_eq!(game_state.game_map.to_string().trim_end(), input.trim_end());
+        assert_eq!(
+            game_state.walk().next(),
+            Some((game_state.guard_position, game_state.direction))
+        );
+    }
+
+    #[test]
+    fn it_jumps_to_the_same_outcome_as_walking() {
+        let input = include_str!("../assets/test-input.txt");
+        let game_state = parse_input_to_game_map(input).unwrap();
+        let table = JumpTable::new(&game_state.game_map);
+        let start = game_state.guard_position;
+        assert_eq!(table.outcome(start, Direction::Up, None), Outcome::Exited);
+        assert_eq!(
+            table.jump(start, Direction::Up, None),
+            Some(start + Direction::Up.step() * 5)
+        );
+        for (point, _) in game_state.game_map.iter() {
+            let walked = crate::outcome(game_state.walk_with_obstacle(point));
+            assert_eq!(table.outcome(start, Direction::Up, Some(point)), walked);
+        }
+        assert_eq!(puzzle_2(&game_state), puzzle_2_walking(&game_state));
     }
 }
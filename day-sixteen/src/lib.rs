@@ -1,83 +1,185 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
+use aoc_common::geometry::{Direction, Point};
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::search::{dijkstra_all, BestRoutes};
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashSet;
+use std::path::Path;
+use std::{fs, io};
+
+pub struct ReindeerGame {
+    pub map: Grid<char>,
+    pub reindeer_pos: Point,
+    pub reindeer_orientation: Direction,
+    pub end_pos: Point,
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub fn parse_input(input: &str) -> Result<ReindeerGame, ParseError> {
+    let map = Grid::parse(input)?;
+    let reindeer_pos = map
+        .find(&'S')
+        .ok_or_else(|| ParseError::end_of_input(input, "start tile `S` not found in map"))?;
+    let end_pos = map
+        .find(&'E')
+        .ok_or_else(|| ParseError::end_of_input(input, "end tile `E` not found in map"))?;
+    Ok(ReindeerGame {
+        map,
+        reindeer_pos,
+        // The reindeer always starts facing east.
+        reindeer_orientation: Direction::Right,
+        end_pos,
+    })
 }
 
-struct ReindeerGame {
-    map: Vec<Vec<char>>,
-    reindeer_pos: (usize, usize),
-    reindeer_orientation: char,
-    end_pos: (usize, usize),
+/// Where the reindeer stands and which way it faces.
+pub type Pose = (Point, Direction);
+
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+/// Every lowest-score way through the maze, or `None` when the end cannot
+/// be reached.
+pub fn best_routes(game: &ReindeerGame) -> Option<BestRoutes<Pose>> {
+    let neighbours = |&(position, direction): &Pose| {
+        let ahead = position + direction;
+        let open = matches!(game.map.get(ahead), Some(&tile) if tile != '#');
+        let forward = open.then_some(((ahead, direction), STEP_COST));
+        [
+            ((position, direction.turn_left()), TURN_COST),
+            ((position, direction.turn_right()), TURN_COST),
+        ]
+        .into_iter()
+        .chain(forward)
+    };
+    dijkstra_all(
+        (game.reindeer_pos, game.reindeer_orientation),
+        neighbours,
+        |&(position, _)| position == game.end_pos,
+    )
+}
+
+/// What [`render`] draws over the maze.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overlay {
+    /// One lowest-score path, each tile showing the way the reindeer leaves it.
+    Path,
+    /// Every tile on any lowest-score path, as `O` like the puzzle text.
+    BestSeats,
 }
 
-fn parse_input(filename: PathBuf) -> ReindeerGame {
-    let mut map = Vec::new();
-    let mut reindeer_pos = (0, 0);
-    let mut end_pos = (0, 0);
-    if let Ok(lines) = read_lines(filename) {
-        for (y, line) in lines.enumerate() {
-            if let Ok(line) = line {
-                let l = line
-                    .chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        if c == 'S' {
-                            reindeer_pos = (x, y);
-                        }
-                        if c == 'E' {
-                            end_pos = (x, y);
-                        }
-                        c
-                    })
-                    .collect::<Vec<char>>();
-                map.push(l);
+/// The maze with `overlay` drawn on it; an unreachable end leaves it bare.
+pub fn render(game: &ReindeerGame, overlay: Overlay) -> String {
+    let mut map = game.map.clone();
+    if let Some(routes) = best_routes(game) {
+        match overlay {
+            Overlay::Path => {
+                for &(position, direction) in &routes.path {
+                    if map[position] == '.' || Direction::from_arrow(map[position]).is_some() {
+                        map[position] = direction.arrow();
+                    }
+                }
+            }
+            Overlay::BestSeats => {
+                for &(position, _) in &routes.states {
+                    map[position] = 'O';
+                }
             }
         }
     }
-    ReindeerGame {
-        map,
-        reindeer_pos,
-        reindeer_orientation: '<',
-        end_pos,
-    }
+    map.to_string()
+}
+
+/// Writes [`render`] to `path` so it can be diffed against the puzzle text.
+pub fn render_to_file(game: &ReindeerGame, overlay: Overlay, path: &Path) -> io::Result<()> {
+    fs::write(path, render(game, overlay))
+}
+
+/// The lowest score, or `None` when the end cannot be reached.
+pub fn puzzle_1(data: &ReindeerGame) -> Option<u64> {
+    best_routes(data).map(|routes| routes.cost)
+}
+
+/// Tiles on at least one lowest-score path, the best seats to watch from.
+pub fn puzzle_2(data: &ReindeerGame) -> Option<usize> {
+    best_routes(data).map(|routes| {
+        let tiles = routes.states.iter().map(|&(position, _)| position);
+        tiles.collect::<HashSet<_>>().len()
+    })
 }
 
-fn puzzle_1(mut data: ReindeerGame) -> i32 {
-    0
+const UNREACHABLE: &str = "the end tile cannot be reached";
+
+pub struct DaySixteen;
+
+impl Solution for DaySixteen {
+    const DAY: u8 = 16;
+    type Input = ReindeerGame;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        match puzzle_1(input) {
+            Some(score) => score.into(),
+            None => Answer::NoAnswer(UNREACHABLE.to_string()),
+        }
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        match puzzle_2(input) {
+            Some(seats) => seats.into(),
+            None => Answer::NoAnswer(UNREACHABLE.to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_1, puzzle_2, render, DaySixteen, Overlay};
+    use aoc_common::solution::{Answer, Solution};
+
+    aoc_common::test_answers!(crate::DaySixteen);
+
+    #[test]
+    fn it_renders_the_best_seats() {
+        let input = include_str!("../assets/test-input-one.txt");
+        let game = parse_input(input).unwrap();
+        let seats = render(&game, Overlay::BestSeats);
+        assert_eq!(seats.matches('O').count(), 45);
+        assert_eq!(seats.lines().nth(1), Some("#.......#....O#"));
+        assert_eq!(seats.lines().nth(13), Some("#O..#.....#OOO#"));
+    }
+
+    #[test]
+    fn it_renders_one_path_with_arrows() {
+        let input = include_str!("../assets/test-input-one.txt");
+        let game = parse_input(input).unwrap();
+        let path = render(&game, Overlay::Path);
+        let arrows = path.chars().filter(|c| "^>v<".contains(*c)).count();
+        assert_eq!(arrows, 35);
+        assert!(path.contains('S') && path.contains('E'));
+    }
 
     #[test]
-    fn it_returns_7036() {
-        let file_path = get_file_path("test-input-one.txt".to_string());
-        let game = parse_input(file_path);
-        let result = puzzle_1(game);
-        assert_eq!(result, 7036);
+    fn it_stays_inside_an_unbordered_maze() {
+        let game = parse_input("S#E\n").unwrap();
+        assert_eq!(puzzle_1(&game), None);
+        assert_eq!(render(&game, Overlay::Path), "S#E\n");
     }
 
     #[test]
-    fn it_returns_11048() {
-        let file_path = get_file_path("test-input-two.txt".to_string());
-        let game = parse_input(file_path);
-        let result = puzzle_1(game);
-        assert_eq!(result, 11048);
+    fn it_gives_up_on_an_enclosed_end() {
+        let input = "\
+#####
+#S..#
+#.###
+#.#E#
+#####
+";
+        let game = parse_input(input).unwrap();
+        assert_eq!(puzzle_2(&game), None);
+        assert!(matches!(DaySixteen.part_one(&game), Answer::NoAnswer(_)));
+        assert_eq!(render(&game, Overlay::Path), input);
     }
 }
@@ -1,220 +1,71 @@
+use aoc_common::geometry::Point;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+pub type TopographicMap = Grid<u8>;
 
-type TopographicMap = Vec<Vec<u8>>;
-
-fn parse_input(filename: PathBuf) -> TopographicMap {
-    let mut data = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let row = line
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap() as u8)
-                    .collect();
-                data.push(row);
-            }
-        }
-    }
-    data
+pub fn parse_input(input: &str) -> Result<TopographicMap, ParseError> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|height| height as u8))
 }
 
-fn pathfinding(
-    y: usize,
-    x: usize,
-    map: &TopographicMap,
-    peaks: &mut HashSet<(usize, usize)>,
-) -> u32 {
-    let mut sum = 0;
-    if map[y][x] == 9 {
-        return if !peaks.contains(&(y, x)) {
-            peaks.insert((y, x));
-            1
-        } else {
-            0
-        };
-    }
-    let elevation = map[y][x] + 1;
-    let right = if x + 1 < map[0].len() {
-        Some(map[y][x + 1])
-    } else {
-        None
-    };
-    let left = if x as i32 - 1 >= 0 {
-        Some(map[y][x - 1])
-    } else {
-        None
-    };
-    let down = if y + 1 < map.len() {
-        Some(map[y + 1][x])
-    } else {
-        None
-    };
-    let up = if y as i32 - 1 >= 0 {
-        Some(map[y - 1][x])
-    } else {
-        None
-    };
-
-    if let Some(height) = right {
-        if height == elevation {
-            sum += pathfinding(y, x + 1, map, peaks)
-        }
-    }
-    if let Some(height) = left {
-        if height == elevation {
-            sum += pathfinding(y, x - 1, map, peaks)
-        }
-    }
-    if let Some(height) = down {
-        if height == elevation {
-            sum += pathfinding(y + 1, x, map, peaks)
-        }
+fn pathfinding(position: Point, map: &TopographicMap, peaks: &mut HashSet<Point>) -> u32 {
+    let elevation = map[position];
+    if elevation == 9 {
+        return u32::from(peaks.insert(position));
     }
-    if let Some(height) = up {
-        if height == elevation {
-            sum += pathfinding(y - 1, x, map, peaks)
-        }
-    }
-    sum
+    map.neighbours4(position)
+        .filter(|(_, height)| **height == elevation + 1)
+        .map(|(next, _)| pathfinding(next, map, peaks))
+        .sum()
 }
 
-fn puzzle_1(data: TopographicMap) -> u32 {
-    let mut result = 0;
-    for (y, row) in data.iter().enumerate() {
-        for (x, position) in row.iter().enumerate() {
-            if *position == 0 {
-                let mut peaks = HashSet::new();
-                let path_count = pathfinding(y, x, &data, &mut peaks);
-                result += path_count;
-            }
-        }
-    }
-    result
+pub fn puzzle_1(data: &TopographicMap) -> u32 {
+    data.iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(trailhead, _)| pathfinding(trailhead, data, &mut HashSet::new()))
+        .sum()
 }
 
-fn pathfinding_2(y: usize, x: usize, map: &TopographicMap) -> u32 {
-    if map[y][x] == 9 {
+fn pathfinding_2(position: Point, map: &TopographicMap) -> u32 {
+    let elevation = map[position];
+    if elevation == 9 {
         return 1;
     }
-    let mut sum = 0;
-    let elevation = map[y][x] + 1;
-    let right = if x + 1 < map[0].len() {
-        Some(map[y][x + 1])
-    } else {
-        None
-    };
-    let left = if x as i32 - 1 >= 0 {
-        Some(map[y][x - 1])
-    } else {
-        None
-    };
-    let down = if y + 1 < map.len() {
-        Some(map[y + 1][x])
-    } else {
-        None
-    };
-    let up = if y as i32 - 1 >= 0 {
-        Some(map[y - 1][x])
-    } else {
-        None
-    };
-
-    if let Some(height) = right {
-        if height == elevation {
-            sum += crate::pathfinding_2(y, x + 1, map)
-        }
-    }
-    if let Some(height) = left {
-        if height == elevation {
-            sum += crate::pathfinding_2(y, x - 1, map)
-        }
-    }
-    if let Some(height) = down {
-        if height == elevation {
-            sum += crate::pathfinding_2(y + 1, x, map)
-        }
-    }
-    if let Some(height) = up {
-        if height == elevation {
-            sum += crate::pathfinding_2(y - 1, x, map)
-        }
-    }
-    sum
+    map.neighbours4(position)
+        .filter(|(_, height)| **height == elevation + 1)
+        .map(|(next, _)| pathfinding_2(next, map))
+        .sum()
 }
 
-fn puzzle_2(data: TopographicMap) -> u32 {
-    let mut result = 0;
-    for (y, row) in data.iter().enumerate() {
-        for (x, position) in row.iter().enumerate() {
-            if *position == 0 {
-                let path_count = pathfinding_2(y, x, &data);
-                result += path_count;
-            }
-        }
-    }
-    result
+pub fn puzzle_2(data: &TopographicMap) -> u32 {
+    data.iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(trailhead, _)| pathfinding_2(trailhead, data))
+        .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1, puzzle_2};
+pub struct DayTen;
 
-    #[test]
-    fn it_returns_1() {
-        let file_path = get_file_path("small-test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 1);
-    }
+impl Solution for DayTen {
+    const DAY: u8 = 10;
+    type Input = TopographicMap;
 
-    #[test]
-    fn it_returns_36() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 36);
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 694);
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
     }
 
-    #[test]
-    fn it_returns_81() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_2(data);
-        assert_eq!(result, 81);
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
     }
+}
 
-    #[test]
-    fn it_returns_puzzle_2_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_2(data);
-        assert_eq!(result, 1497);
-    }
+#[cfg(test)]
+mod tests {
+    aoc_common::test_answers!(crate::DayTen);
 }
@@ -1,23 +1,6 @@
+use aoc_common::parse::{parse_number, split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
 use regex::Regex;
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
 #[derive(Clone, Copy, Debug)]
 struct Button {
@@ -32,70 +15,97 @@ struct Prize {
 }
 
 #[derive(Clone, Copy, Debug)]
-struct MachineClaw {
+pub struct MachineClaw {
     button_a: Button,
     button_b: Button,
     prize: Prize,
 }
 
-fn parse_input_to_machines(filename: PathBuf) -> Vec<MachineClaw> {
+fn parse_values(
+    index: usize,
+    line: &str,
+    values: &str,
+    regex: &Regex,
+) -> Result<(i32, i32), ParseError> {
+    let caps = regex
+        .captures(values)
+        .ok_or_else(|| ParseError::at(index, line, values, "expected X and Y values"))?;
+    Ok((
+        parse_number(index, line, &caps["x"])?,
+        parse_number(index, line, &caps["y"])?,
+    ))
+}
+
+/// The lines of one machine read so far.
+#[derive(Default)]
+struct Block {
+    button_a: Option<Button>,
+    button_b: Option<Button>,
+    prize: Option<Prize>,
+}
+
+impl Block {
+    fn is_empty(&self) -> bool {
+        self.button_a.is_none() && self.button_b.is_none() && self.prize.is_none()
+    }
+
+    fn finish(self, missing: impl Fn(&str) -> ParseError) -> Result<MachineClaw, ParseError> {
+        Ok(MachineClaw {
+            button_a: self.button_a.ok_or_else(|| missing("Button A"))?,
+            button_b: self.button_b.ok_or_else(|| missing("Button B"))?,
+            prize: self.prize.ok_or_else(|| missing("Prize"))?,
+        })
+    }
+}
+
+fn missing_line(name: &str) -> String {
+    format!("machine is missing its `{name}:` line")
+}
+
+pub fn parse_input_to_machines(input: &str) -> Result<Vec<MachineClaw>, ParseError> {
     let mut machines = Vec::new();
     let button_regex = Regex::new(r" X\+(?<x>[0-9]{1,2}), Y\+(?<y>[0-9]{1,2})").unwrap();
     let prize_regex = Regex::new(r"X=(?<x>[0-9]{1,5}), Y=(?<y>[0-9]{1,5})").unwrap();
-    if let Ok(lines) = read_lines(filename) {
-        let mut button_a = Button {
-            right_move: 0,
-            forward_move: 0,
-        };
-        let mut button_b = Button {
-            right_move: 0,
-            forward_move: 0,
-        };
-        let mut prize = Prize { x: 0, y: 0 };
-        for line in lines {
-            if let Ok(line) = line {
-                if line.is_empty() {
-                    machines.push(MachineClaw {
-                        button_a,
-                        button_b,
-                        prize,
-                    });
+    let mut block = Block::default();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            if !block.is_empty() {
+                let missing = |name: &str| ParseError::at(i, line, line, missing_line(name));
+                machines.push(std::mem::take(&mut block).finish(missing)?);
+            }
+            continue;
+        }
+        let (instruction, values) = split_once(i, line, ":")?;
+        match instruction {
+            "Button A" | "Button B" => {
+                let (right_move, forward_move) = parse_values(i, line, values, &button_regex)?;
+                let button = Some(Button {
+                    right_move,
+                    forward_move,
+                });
+                if instruction == "Button A" {
+                    block.button_a = button;
                 } else {
-                    let instruction = line.split(":").collect::<Vec<_>>();
-                    match instruction[0] {
-                        "Button A" => {
-                            let caps = button_regex.captures(&instruction[1]).unwrap();
-                            button_a.right_move =
-                                caps.name("x").unwrap().as_str().parse::<i32>().unwrap();
-                            button_a.forward_move =
-                                caps.name("y").unwrap().as_str().parse::<i32>().unwrap();
-                        }
-                        "Button B" => {
-                            let caps = button_regex.captures(&instruction[1]).unwrap();
-                            button_b.right_move =
-                                caps.name("x").unwrap().as_str().parse::<i32>().unwrap();
-                            button_b.forward_move =
-                                caps.name("y").unwrap().as_str().parse::<i32>().unwrap();
-                        }
-                        "Prize" => {
-                            let caps = prize_regex.captures(&instruction[1]).unwrap();
-                            prize.x = caps.name("x").unwrap().as_str().parse::<i32>().unwrap();
-                            prize.y = caps.name("y").unwrap().as_str().parse::<i32>().unwrap();
-                        }
-                        _ => {
-                            panic!("Unknown instruction {}", instruction[0])
-                        }
-                    }
+                    block.button_b = button;
                 }
             }
+            "Prize" => {
+                let (x, y) = parse_values(i, line, values, &prize_regex)?;
+                block.prize = Some(Prize { x, y });
+            }
+            _ => {
+                return Err(ParseError::at(i, line, instruction, "unknown instruction"));
+            }
         }
     }
-    machines
+    if !block.is_empty() {
+        machines.push(block.finish(|name| ParseError::end_of_input(input, missing_line(name)))?);
+    }
+    Ok(machines)
 }
 
-fn puzzle_1(machines: Vec<MachineClaw>) -> i32 {
+pub fn puzzle_1(machines: &[MachineClaw]) -> i32 {
     let mut result = 0;
-    println!("machines: {:?}", machines.len());
     for machine in machines {
         let x1 = machine.button_a.right_move;
         let x2 = machine.button_a.forward_move;
@@ -116,23 +126,52 @@ fn puzzle_1(machines: Vec<MachineClaw>) -> i32 {
     result
 }
 
+pub struct DayThirteen;
+
+impl Solution for DayThirteen {
+    const DAY: u8 = 13;
+    type Input = Vec<MachineClaw>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_to_machines(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input_to_machines, puzzle_1};
+    use crate::{parse_input_to_machines, puzzle_1};
+
+    aoc_common::test_answers!(crate::DayThirteen);
+
+    #[test]
+    fn it_reports_an_unknown_instruction() {
+        let err = parse_input_to_machines("Button C: X+94, Y+34").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.text.as_str()),
+            (1, 1, "Button C")
+        );
+    }
 
     #[test]
-    fn it_returns_480() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input_to_machines(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 480);
+    fn it_keeps_the_last_machine_without_a_trailing_blank_line() {
+        let input = include_str!("../assets/test-input.txt").trim_end();
+        let machines = parse_input_to_machines(input).unwrap();
+        assert_eq!(machines.len(), 4);
+        assert_eq!(puzzle_1(&machines), 480);
     }
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input_to_machines(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 31065);
+    fn it_reports_a_machine_missing_a_line() {
+        let input = "Button A: X+94, Y+34\nPrize: X=8400, Y=5400\n\n";
+        let err = parse_input_to_machines(input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert!(err.message.contains("Button B"));
+        let err = parse_input_to_machines("Button A: X+94, Y+34").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("Button B"));
     }
 }
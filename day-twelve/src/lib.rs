@@ -1,51 +1,130 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-type Garden = Vec<Vec<char>>;
-
-fn parse_input(filename: PathBuf) -> Garden {
-    let mut data = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                let row = line.chars().collect::<Vec<_>>();
-                data.push(row);
+use aoc_common::geometry::{Direction, Point};
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+pub type Garden = Grid<char>;
+
+pub fn parse_input(input: &str) -> Result<Garden, ParseError> {
+    Grid::parse(input)
+}
+
+/// A connected patch of one plant type.
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub plant: char,
+    pub cells: HashSet<Point>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Cell edges that border another plant or the edge of the garden.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|&cell| Direction::ALL.map(|direction| cell + direction))
+            .filter(|next| !self.cells.contains(next))
+            .count()
+    }
+
+    /// Straight runs of fence, counted as corners since a closed fence has
+    /// as many sides as corners.
+    pub fn sides(&self) -> usize {
+        let inside = |point: Point| self.cells.contains(&point);
+        self.cells
+            .iter()
+            .flat_map(|&cell| {
+                Direction::ALL.map(|direction| {
+                    let turned = direction.turn_right();
+                    let (ahead, beside) = (inside(cell + direction), inside(cell + turned));
+                    let diagonal = inside(cell + direction + turned);
+                    let outer = !ahead && !beside;
+                    let inner = ahead && beside && !diagonal;
+                    outer || inner
+                })
+            })
+            .filter(|&corner| corner)
+            .count()
+    }
+}
+
+/// Splits the garden into regions by flood filling each unclaimed cell.
+pub fn regions(garden: &Garden) -> Vec<Region> {
+    let mut claimed = garden.map(|_| false);
+    let mut regions = Vec::new();
+    for (start, &plant) in garden.iter() {
+        if claimed[start] {
+            continue;
+        }
+        claimed[start] = true;
+        let mut cells = HashSet::from([start]);
+        let mut pending = vec![start];
+        while let Some(cell) = pending.pop() {
+            for (next, &other) in garden.neighbours4(cell) {
+                if other == plant && !claimed[next] {
+                    claimed[next] = true;
+                    cells.insert(next);
+                    pending.push(next);
+                }
             }
         }
+        regions.push(Region { plant, cells });
     }
-    data
+    regions
 }
-fn puzzle_1(data: Garden) -> u32 {
-    0
+
+pub fn puzzle_1(data: &Garden) -> usize {
+    regions(data)
+        .iter()
+        .map(|region| region.area() * region.perimeter())
+        .sum()
+}
+
+pub fn puzzle_2(data: &Garden) -> usize {
+    regions(data)
+        .iter()
+        .map(|region| region.area() * region.sides())
+        .sum()
+}
+
+pub struct DayTwelve;
+
+impl Solution for DayTwelve {
+    const DAY: u8 = 12;
+    type Input = Garden;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Answer {
+        puzzle_2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, regions};
+
+    aoc_common::test_answers!(crate::DayTwelve);
 
     #[test]
-    fn it_returns_1930() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 1930);
+    fn it_measures_regions_with_holes() {
+        let data = parse_input("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n").unwrap();
+        let regions = regions(&data);
+        assert_eq!(regions.len(), 5);
+        let outer = regions.iter().find(|region| region.plant == 'O').unwrap();
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
     }
 }
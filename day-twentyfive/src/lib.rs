@@ -1,87 +1,53 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+use aoc_common::solution::{Answer, Solution};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+pub type Locks = Vec<Vec<usize>>;
 
-type Locks = Vec<Vec<usize>>;
+pub type Keys = Vec<Vec<usize>>;
 
-type Keys = Vec<Vec<usize>>;
+pub type Schemes = Vec<Grid<char>>;
 
-type Schemes = Vec<Vec<Vec<char>>>;
-
-fn parse_input(filename: PathBuf) -> Schemes {
+pub fn parse_input(input: &str) -> Result<Schemes, ParseError> {
+    let mut lines = input.lines().enumerate().peekable();
     let mut schemes = Vec::new();
-    let mut scheme = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                if line.is_empty() {
-                    schemes.push(scheme);
-                    scheme = Vec::new();
-                    continue;
-                }
-                let inscription = line.chars().collect::<Vec<char>>();
-                scheme.push(inscription);
-            }
+    while lines.peek().is_some() {
+        let block = lines.by_ref().take_while(|(_, line)| !line.is_empty());
+        let scheme = Grid::parse_lines(block, |c| matches!(c, '#' | '.').then_some(c))?;
+        if !scheme.is_empty() {
+            schemes.push(scheme);
         }
     }
-    schemes
+    Ok(schemes)
 }
 
-fn parse_schemes_to_locks_and_keys(schemes: Vec<Vec<Vec<char>>>) -> (Locks, Keys) {
-    let mut locks_scheme = Vec::new();
-    let mut keys_scheme = Vec::new();
+fn parse_schemes_to_locks_and_keys(schemes: &Schemes) -> (Locks, Keys) {
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
     for scheme in schemes {
-        if scheme[0].iter().all(|c| *c == '#') {
-            let lock = scheme[1..].to_vec();
-            locks_scheme.push(lock);
+        if scheme.row(0).iter().all(|c| *c == '#') {
+            locks.push(pin_heights(scheme));
         }
-        if scheme[0].iter().all(|c| *c == '.') {
-            let key = scheme[..scheme.len() - 1].to_vec();
-            keys_scheme.push(key);
+        if scheme.row(0).iter().all(|c| *c == '.') {
+            keys.push(pin_heights(scheme));
         }
     }
-
-    (
-        build_locks_and_keys(locks_scheme),
-        build_locks_and_keys(keys_scheme),
-    )
+    (locks, keys)
 }
 
-fn build_locks_and_keys(schemes: Vec<Vec<Vec<char>>>) -> Vec<Vec<usize>> {
-    let mut kl = Vec::new();
-    for scheme in schemes {
-        let mut sch = Vec::new();
-        for x in 0..scheme[0].len() {
-            let mut col = Vec::new();
-            for y in 0..scheme.len() {
-                col.push(scheme[y][x]);
-            }
-            let count = col.iter().filter(|c| **c == '#').count();
-            sch.push(count);
-        }
-        kl.push(sch);
-    }
-    kl
+/// Filled cells per column, not counting the solid base row. A column with no
+/// base at all counts as empty rather than underflowing.
+fn pin_heights(scheme: &Grid<char>) -> Vec<usize> {
+    (0..scheme.width())
+        .map(|x| {
+            let filled = scheme.column(x).filter(|c| **c == '#').count();
+            filled.saturating_sub(1)
+        })
+        .collect()
 }
 
-fn puzzle_1(data: Schemes) -> u64 {
-    let (locks, keys) = parse_schemes_to_locks_and_keys(data.clone());
+pub fn puzzle_1(data: &Schemes) -> u64 {
+    let (locks, keys) = parse_schemes_to_locks_and_keys(data);
     let mut count: u64 = 0;
     for lock in locks {
         for key in &keys {
@@ -93,23 +59,30 @@ fn puzzle_1(data: Schemes) -> u64 {
     count
 }
 
+pub struct DayTwentyFive;
+
+impl Solution for DayTwentyFive {
+    const DAY: u8 = 25;
+    type Input = Schemes;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_1};
 
-    #[test]
-    fn it_returns_3() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 3);
-    }
+    aoc_common::test_answers!(crate::DayTwentyFive);
 
     #[test]
-    fn it_returns_puzzle1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 3255);
+    fn it_counts_a_column_without_base_as_empty() {
+        let data = parse_input("###\n#..\n...\n\n...\n...\n#.#\n").unwrap();
+        assert_eq!(puzzle_1(&data), 1);
     }
 }
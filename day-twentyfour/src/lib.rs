@@ -1,118 +1,181 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
-
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
+use std::collections::{HashMap, VecDeque};
+
+use aoc_common::parse::{split_once, ParseError};
+use aoc_common::solution::{Answer, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    And,
+    Or,
+    Xor,
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+impl Operator {
+    pub fn apply(self, left: bool, right: bool) -> bool {
+        match self {
+            Operator::And => left & right,
+            Operator::Or => left | right,
+            Operator::Xor => left ^ right,
+        }
+    }
 }
 
-type Instructions = VecDeque<Vec<String>>;
-type Wires = HashMap<String, String>;
+/// A gate writing `left <operator> right` to the `out` wire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub left: String,
+    pub operator: Operator,
+    pub right: String,
+    pub out: String,
+}
 
-fn parse_input(filename: PathBuf) -> (Instructions, Wires) {
+pub type Instructions = VecDeque<Gate>;
+pub type Wires = HashMap<String, bool>;
+
+pub fn parse_input(input: &str) -> Result<(Instructions, Wires), ParseError> {
     let mut instructions = VecDeque::new();
     let mut wires = HashMap::new();
     let mut parse_wires = false;
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                if line.is_empty() {
-                    parse_wires = true;
-                    continue;
-                }
-                if parse_wires {
-                    let instruction = line
-                        .split_whitespace()
-                        .filter(|s| s != &"->")
-                        .map(|s| s.to_string())
-                        .collect::<Vec<_>>();
-                    instructions.push_back(instruction);
-                } else {
-                    let [input, value] = line.split(": ").collect::<Vec<_>>()[..] else {
-                        panic!("Failed to parse line");
-                    };
-                    wires.insert(input.to_string(), value.to_string());
-                }
-            }
+    let mut gate_inputs = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            parse_wires = true;
+            continue;
+        }
+        if parse_wires {
+            let instruction = line
+                .split_whitespace()
+                .filter(|s| s != &"->")
+                .collect::<Vec<_>>();
+            let [left, operator, right, out] = instruction[..] else {
+                return Err(ParseError::at(
+                    i,
+                    line,
+                    line,
+                    "expected `<wire> <gate> <wire> -> <wire>`",
+                ));
+            };
+            let operator = match operator {
+                "AND" => Operator::And,
+                "OR" => Operator::Or,
+                "XOR" => Operator::Xor,
+                _ => return Err(ParseError::at(i, line, operator, "expected AND, OR or XOR")),
+            };
+            gate_inputs.extend([(i, line, left), (i, line, right)]);
+            instructions.push_back(Gate {
+                left: left.to_string(),
+                operator,
+                right: right.to_string(),
+                out: out.to_string(),
+            });
+        } else {
+            let (input, value) = split_once(i, line, ": ")?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(ParseError::at(i, line, value, "expected 0 or 1")),
+            };
+            wires.insert(input.to_string(), value);
         }
     }
-    (instructions, wires)
+    let driven =
+        |wire: &str| wires.contains_key(wire) || instructions.iter().any(|gate| gate.out == wire);
+    if let Some(&(i, line, wire)) = gate_inputs.iter().find(|(_, _, wire)| !driven(wire)) {
+        return Err(ParseError::at(
+            i,
+            line,
+            wire,
+            "wire has no initial value and no gate drives it",
+        ));
+    }
+    Ok((instructions, wires))
 }
 
-fn puzzle_1(data: (Instructions, Wires)) -> u64 {
+/// The number on the `z` wires, or `None` when the gates feed each other in
+/// a loop and never settle.
+pub fn puzzle_1(data: (Instructions, Wires)) -> Option<u64> {
     let (mut instructions, mut wires) = data;
 
-    while let Some(ins) = instructions.pop_front() {
-        let [left, op, right, out] = &ins[..] else {
-            panic!("Failed to parse instruction")
-        };
-        if wires.get(left).is_none() || wires.get(right).is_none() {
-            instructions.push_back(ins);
-            continue;
-        }
-        if let Some(left_value) = wires.get(left) {
-            if let Some(right_value) = wires.get(right) {
-                let out_value = match op.as_str() {
-                    "AND" => {
-                        left_value.parse::<i32>().unwrap() & right_value.parse::<i32>().unwrap()
-                    }
-                    "OR" => {
-                        left_value.parse::<i32>().unwrap() | right_value.parse::<i32>().unwrap()
-                    }
-                    "XOR" => {
-                        left_value.parse::<i32>().unwrap() ^ right_value.parse::<i32>().unwrap()
-                    }
-                    _ => panic!("Unknown operator"),
-                };
-                wires.insert(out.to_string(), out_value.to_string());
+    // Gates put back in a row; once every queued gate has been, none can fire.
+    let mut waiting = 0;
+    while let Some(gate) = instructions.pop_front() {
+        let (Some(&left), Some(&right)) = (wires.get(&gate.left), wires.get(&gate.right)) else {
+            instructions.push_back(gate);
+            waiting += 1;
+            if waiting == instructions.len() {
+                return None;
             }
-        }
+            continue;
+        };
+        waiting = 0;
+        wires.insert(gate.out, gate.operator.apply(left, right));
     }
     let mut filtered_wires = wires
         .iter()
         .filter(|p| p.0.starts_with("z"))
-        .map(|x| (x.0, x.1))
         .collect::<Vec<_>>();
     filtered_wires.sort();
-    let bin = filtered_wires
+    let number = filtered_wires
         .iter()
         .rev()
-        .map(|x| x.1.to_string())
-        .collect::<Vec<String>>()
-        .join("");
-    u64::from_str_radix(&bin, 2).unwrap()
+        .fold(0, |number, (_, &bit)| number << 1 | bit as u64);
+    Some(number)
+}
+
+pub struct DayTwentyFour;
+
+impl Solution for DayTwentyFour {
+    const DAY: u8 = 24;
+    type Input = (Instructions, Wires);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        match puzzle_1(input.clone()) {
+            Some(number) => number.into(),
+            None => Answer::NoAnswer("the gates loop and never settle".to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+    use crate::{parse_input, puzzle_1, Operator};
+
+    aoc_common::test_answers!(crate::DayTwentyFour);
+
+    #[test]
+    fn it_parses_gates_and_wire_values() {
+        let (gates, wires) = parse_input("x00: 1\ny00: 0\n\nx00 XOR y00 -> z00").unwrap();
+        assert_eq!(gates[0].operator, Operator::Xor);
+        assert_eq!(gates[0].out, "z00");
+        assert_eq!((wires["x00"], wires["y00"]), (true, false));
+    }
+
+    #[test]
+    fn it_rejects_wire_values_other_than_bits() {
+        let err = parse_input("x00: 7\n\nx00 AND x00 -> z00").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 6, "7"));
+    }
+
+    #[test]
+    fn it_rejects_unknown_operators() {
+        let err = parse_input("x00: 1\n\nx00 NAND x00 -> z00").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 5, "NAND"));
+    }
 
     #[test]
-    fn it_returns_2024() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 2024);
+    fn it_rejects_gates_reading_undriven_wires() {
+        let err = parse_input("x00: 1\n\nx00 AND x0O -> z00").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 9, "x0O"));
     }
 
     #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 42049478636360);
+    fn it_gives_up_on_gates_feeding_each_other() {
+        let data =
+            parse_input("x00: 1\n\nx00 AND a -> b\nx00 OR b -> a\nx00 XOR x00 -> z00").unwrap();
+        assert_eq!(puzzle_1(data), None);
     }
 }
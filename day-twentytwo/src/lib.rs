@@ -1,34 +1,12 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::BufRead;
-use std::path::{Path, PathBuf};
-use std::{env, io};
+use aoc_common::parse::{parse_number, ParseError};
+use aoc_common::solution::{Answer, Solution};
 
-fn get_file_path(filename: String) -> PathBuf {
-    let current_dir = env::current_dir().expect("Attempt to get current dir");
-    current_dir
-        .join("assets")
-        .join(Path::new(filename.as_str()))
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn parse_input(filename: PathBuf) -> Vec<u64> {
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
     let mut secret_numbers = Vec::new();
-    if let Ok(lines) = read_lines(filename) {
-        for line in lines {
-            if let Ok(line) = line {
-                secret_numbers.push(line.parse::<u64>().unwrap());
-            }
-        }
+    for (i, line) in input.lines().enumerate() {
+        secret_numbers.push(parse_number(i, line, line)?);
     }
-    secret_numbers
+    Ok(secret_numbers)
 }
 
 fn first_step(secret_number: u64) -> u64 {
@@ -57,10 +35,10 @@ fn prune(value: u64) -> u64 {
     value % 16777216
 }
 
-fn puzzle_1(data: Vec<u64>) -> u64 {
+pub fn puzzle_1(data: &[u64]) -> u64 {
     let mut secrets_nth = Vec::new();
     for secret_number in data {
-        let nth_number = (1..=2000).fold(secret_number, |mut acc, _| {
+        let nth_number = (1..=2000).fold(*secret_number, |mut acc, _| {
             acc = first_step(acc);
             acc = second_step(acc);
             acc = third_step(acc);
@@ -71,23 +49,22 @@ fn puzzle_1(data: Vec<u64>) -> u64 {
     secrets_nth.iter().sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_file_path, parse_input, puzzle_1};
+pub struct DayTwentyTwo;
 
-    #[test]
-    fn it_returns_37327623() {
-        let file_path = get_file_path("test-input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 37327623);
+impl Solution for DayTwentyTwo {
+    const DAY: u8 = 22;
+    type Input = Vec<u64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn it_returns_puzzle_1_score() {
-        let file_path = get_file_path("input.txt".to_string());
-        let data = parse_input(file_path);
-        let result = puzzle_1(data);
-        assert_eq!(result, 17724064040);
+    fn part_one(&self, input: &Self::Input) -> Answer {
+        puzzle_1(input).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    aoc_common::test_answers!(crate::DayTwentyTwo);
+}